@@ -0,0 +1,127 @@
+//! Cancel asynchronous reads with an [`AbortSignal`].
+//!
+//! Every request for file data runs in its own local task. With a signal, the task stops waiting
+//! for the request as soon as the signal is aborted, and dropping an in-flight `reqwest` request
+//! aborts the underlying `fetch`.
+//!
+//! [`AbortSignal`]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal
+
+use std::future::Future;
+use std::pin::{Pin, pin};
+use std::task::{Context, Poll};
+
+use futures::FutureExt;
+use futures::channel::oneshot;
+use futures::future::{Either, select};
+use wasm_bindgen::prelude::*;
+
+use crate::error::{ParquetWasmError, Result};
+
+/// An `AbortSignal` passed from JS.
+#[derive(Clone, Debug)]
+pub struct Signal(web_sys::AbortSignal);
+
+/// Safety: This is not in fact thread-safe. Do not attempt to use this in work-stealing
+/// async runtimes / multi-threaded environments
+///
+/// Like `web_sys::Blob` in `WrappedFile`, the signal is held by readers that must implement
+/// `AsyncFileReader`, which requires `Send`. It is only ever used from local tasks.
+unsafe impl Send for Signal {}
+unsafe impl Sync for Signal {}
+
+impl Signal {
+    /// Parse the `signal` key of an options object.
+    pub(crate) fn from_options(
+        options: &JsValue,
+    ) -> std::result::Result<Option<Self>, serde_wasm_bindgen::Error> {
+        let value = js_sys::Reflect::get(options, &"signal".into()).unwrap_or(JsValue::UNDEFINED);
+        if value.is_undefined() || value.is_null() {
+            return Ok(None);
+        }
+        value
+            .dyn_into::<web_sys::AbortSignal>()
+            .map(|signal| Some(Self(signal)))
+            .map_err(|_| serde_wasm_bindgen::Error::new("signal must be an AbortSignal"))
+    }
+
+    pub fn aborted(&self) -> bool {
+        self.0.aborted()
+    }
+}
+
+/// Return an abort error if the signal has already been aborted.
+pub(crate) fn check_aborted(signal: Option<&Signal>) -> Result<()> {
+    match signal {
+        Some(signal) if signal.aborted() => Err(ParquetWasmError::Aborted),
+        _ => Ok(()),
+    }
+}
+
+/// Report an error that happened after the signal was aborted as an abort error.
+///
+/// Errors from cancelled requests pass through the Parquet reader, which only keeps their
+/// message.
+pub(crate) fn abort_error(signal: Option<&Signal>, err: ParquetWasmError) -> ParquetWasmError {
+    match signal {
+        Some(signal) if signal.aborted() => ParquetWasmError::Aborted,
+        _ => err,
+    }
+}
+
+/// Run `future` until it completes, or until the signal is aborted, in which case `future` is
+/// dropped and `None` is returned.
+///
+/// This must be called from a local task, as it listens for the abort event of the signal.
+pub(crate) async fn abortable<F: Future>(signal: Option<&Signal>, future: F) -> Option<F::Output> {
+    let Some(signal) = signal else {
+        return Some(future.await);
+    };
+    if signal.aborted() {
+        return None;
+    }
+    match select(pin!(future), AbortListener::new(&signal.0)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+/// A future that resolves once a signal is aborted.
+///
+/// The event listener is removed when this is dropped, so that a long-lived signal does not
+/// accumulate a listener for every request.
+struct AbortListener {
+    signal: web_sys::AbortSignal,
+    callback: Closure<dyn FnMut()>,
+    receiver: oneshot::Receiver<()>,
+}
+
+impl AbortListener {
+    fn new(signal: &web_sys::AbortSignal) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let callback: Closure<dyn FnMut()> = Closure::once(move || {
+            let _ = sender.send(());
+        });
+        let _ = signal.add_event_listener_with_callback("abort", callback.as_ref().unchecked_ref());
+        Self {
+            signal: signal.clone(),
+            callback,
+            receiver,
+        }
+    }
+}
+
+impl Future for AbortListener {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.receiver.poll_unpin(cx).map(|_| ())
+    }
+}
+
+impl Drop for AbortListener {
+    fn drop(&mut self) {
+        let _ = self
+            .signal
+            .remove_event_listener_with_callback("abort", self.callback.as_ref().unchecked_ref());
+    }
+}
@@ -0,0 +1,204 @@
+//! Check values against the split block bloom filters stored in a Parquet file.
+//!
+//! Bloom filters are stored separately from the column data, so checking them only requires
+//! fetching a few kilobytes per column chunk.
+
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    Decimal32Type, Decimal64Type, Decimal128Type, Decimal256Type, Float32Type, Float64Type,
+    Int32Type, Int64Type,
+};
+use arrow_schema::DataType;
+use futures::{StreamExt, TryStreamExt, stream};
+use parquet::arrow::arrow_reader::ArrowReaderMetadata;
+use parquet::arrow::async_reader::{AsyncFileReader, ParquetRecordBatchStreamBuilder};
+use parquet::arrow::{ProjectionMask, parquet_to_arrow_schema_by_columns};
+use parquet::basic::Type as PhysicalType;
+use parquet::bloom_filter::Sbbf;
+use parquet::errors::ParquetError;
+use parquet::schema::types::SchemaDescriptor;
+
+use crate::error::{ParquetWasmError, Result};
+use crate::filter::FilterValue;
+
+/// Return the subset of `row_groups` whose bloom filter for `column` may contain any of
+/// `values`.
+///
+/// `column` is the dotted path of a leaf column. Row groups without a bloom filter for the column
+/// are always returned, as are all row groups if a value cannot be converted to the physical type
+/// of the column. The bloom filters of up to `concurrency` row groups are fetched concurrently.
+pub async fn prune_row_groups_with_bloom_filters<T>(
+    reader: T,
+    meta: &ArrowReaderMetadata,
+    column: &str,
+    values: &[FilterValue],
+    row_groups: Vec<usize>,
+    concurrency: usize,
+) -> Result<Vec<usize>>
+where
+    T: AsyncFileReader + Clone + Unpin + Send + 'static,
+{
+    let num_row_groups = meta.metadata().num_row_groups();
+    if let Some(row_group) = row_groups.iter().find(|i| **i >= num_row_groups) {
+        return Err(ParquetError::General(format!(
+            "Row group {row_group} out of bounds for file with {num_row_groups} row groups"
+        ))
+        .into());
+    }
+
+    let parquet_schema = meta.parquet_schema();
+    let column_idx = parquet_schema
+        .columns()
+        .iter()
+        .position(|col| col.path().string() == column)
+        .ok_or_else(|| ParquetWasmError::UnknownColumn(column.to_string()))?;
+
+    let physical_values = values
+        .iter()
+        .map(|value| PhysicalValue::try_new(value, parquet_schema, column_idx))
+        .collect::<Option<Vec<_>>>();
+    let Some(physical_values) = physical_values else {
+        return Ok(row_groups);
+    };
+
+    let bloom_filters = stream::iter(row_groups.iter().map(|row_group| {
+        let mut builder =
+            ParquetRecordBatchStreamBuilder::new_with_metadata(reader.clone(), meta.clone());
+        let row_group = *row_group;
+        async move {
+            builder
+                .get_row_group_column_bloom_filter(row_group, column_idx)
+                .await
+        }
+    }))
+    .buffered(concurrency.max(1))
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    Ok(row_groups
+        .into_iter()
+        .zip(bloom_filters)
+        .filter(|(_, bloom_filter)| match bloom_filter {
+            Some(bloom_filter) => physical_values
+                .iter()
+                .any(|value| value.check(bloom_filter)),
+            None => true,
+        })
+        .map(|(row_group, _)| row_group)
+        .collect())
+}
+
+/// A filter value converted to the physical representation that is hashed into the bloom filter.
+enum PhysicalValue {
+    Boolean(bool),
+    Int32(i32),
+    Int64(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+}
+
+impl PhysicalValue {
+    fn try_new(
+        value: &FilterValue,
+        parquet_schema: &SchemaDescriptor,
+        column_idx: usize,
+    ) -> Option<Self> {
+        // Go through the logical Arrow type first, so that e.g. a date string can be checked
+        // against a date column stored as INT32
+        let data_type = leaf_data_type(parquet_schema, column_idx)?;
+        let array = value.to_scalar(&data_type).ok()?.into_inner();
+        let physical_array = |data_type: &DataType| -> Option<ArrayRef> {
+            let array = cast(&array, data_type).ok()?;
+            (!array.is_null(0)).then_some(array)
+        };
+
+        // Decimals are stored as their unscaled value, which a cast to an integer would rescale
+        if let Some(unscaled) = unscaled_decimal(&array) {
+            return match parquet_schema.column(column_idx).physical_type() {
+                PhysicalType::INT32 => i32::try_from(unscaled?).ok().map(Self::Int32),
+                PhysicalType::INT64 => i64::try_from(unscaled?).ok().map(Self::Int64),
+                _ => None,
+            };
+        }
+
+        let physical_value = match parquet_schema.column(column_idx).physical_type() {
+            PhysicalType::BOOLEAN => {
+                Self::Boolean(physical_array(&DataType::Boolean)?.as_boolean().value(0))
+            }
+            PhysicalType::INT32 => Self::Int32(
+                physical_array(&DataType::Int32)?
+                    .as_primitive::<Int32Type>()
+                    .value(0),
+            ),
+            PhysicalType::INT64 => Self::Int64(
+                physical_array(&DataType::Int64)?
+                    .as_primitive::<Int64Type>()
+                    .value(0),
+            ),
+            PhysicalType::FLOAT => Self::Float(
+                physical_array(&DataType::Float32)?
+                    .as_primitive::<Float32Type>()
+                    .value(0),
+            ),
+            PhysicalType::DOUBLE => Self::Double(
+                physical_array(&DataType::Float64)?
+                    .as_primitive::<Float64Type>()
+                    .value(0),
+            ),
+            PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => Self::Bytes(
+                physical_array(&DataType::Binary)?
+                    .as_binary::<i32>()
+                    .value(0)
+                    .to_vec(),
+            ),
+            PhysicalType::INT96 => return None,
+        };
+        Some(physical_value)
+    }
+
+    fn check(&self, bloom_filter: &Sbbf) -> bool {
+        match self {
+            Self::Boolean(value) => bloom_filter.check(value),
+            Self::Int32(value) => bloom_filter.check(value),
+            Self::Int64(value) => bloom_filter.check(value),
+            Self::Float(value) => bloom_filter.check(value),
+            Self::Double(value) => bloom_filter.check(value),
+            Self::Bytes(value) => bloom_filter.check(value),
+        }
+    }
+}
+
+/// The unscaled value of a single decimal, or `None` if `array` is not a decimal array.
+///
+/// The inner value is `None` if the decimal is null or does not fit in an `i128`.
+fn unscaled_decimal(array: &ArrayRef) -> Option<Option<i128>> {
+    let unscaled = match array.data_type() {
+        DataType::Decimal32(_, _) => Some(array.as_primitive::<Decimal32Type>().value(0).into()),
+        DataType::Decimal64(_, _) => Some(array.as_primitive::<Decimal64Type>().value(0).into()),
+        DataType::Decimal128(_, _) => Some(array.as_primitive::<Decimal128Type>().value(0)),
+        DataType::Decimal256(_, _) => array.as_primitive::<Decimal256Type>().value(0).to_i128(),
+        _ => return None,
+    };
+    Some(unscaled.filter(|_| !array.is_null(0)))
+}
+
+/// The Arrow data type of a single leaf column
+fn leaf_data_type(parquet_schema: &SchemaDescriptor, column_idx: usize) -> Option<DataType> {
+    let mask = ProjectionMask::leaves(parquet_schema, [column_idx]);
+    let schema = parquet_to_arrow_schema_by_columns(parquet_schema, mask, None).ok()?;
+
+    // Only a single leaf is projected, so every nested type has exactly one child
+    let mut data_type = schema.fields().first()?.data_type().clone();
+    loop {
+        data_type = match data_type {
+            DataType::Struct(fields) => fields.first()?.data_type().clone(),
+            DataType::List(field)
+            | DataType::LargeList(field)
+            | DataType::FixedSizeList(field, _)
+            | DataType::Map(field, _) => field.data_type().clone(),
+            other => return Some(other),
+        };
+    }
+}
@@ -0,0 +1,134 @@
+//! Buffers of Parquet data that are allocated in WebAssembly memory.
+//!
+//! Passing a `Uint8Array` to a read function copies the entire file into WebAssembly memory. A
+//! [`ParquetBuffer`] is allocated in WebAssembly memory up front and filled from JS, so the data
+//! only ever exists once in WebAssembly memory and can be read multiple times without copying.
+
+use std::cell::RefCell;
+
+use bytes::Bytes;
+use js_sys::{ArrayBuffer, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+
+use crate::error::WasmResult;
+use crate::utils::assert_parquet_file_not_empty;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ParquetInput: &'static str = r#"
+export type ParquetInput = Uint8Array | ArrayBuffer;
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Parquet data in either JS or WebAssembly memory
+    #[wasm_bindgen(typescript_type = "ParquetInput")]
+    pub type ParquetInput;
+}
+
+/// The data of a [`ParquetBuffer`].
+enum BufferData {
+    /// Owned by the buffer, and written to from JS through views
+    Writable(Vec<u8>),
+    /// Shared with the readers of the buffer, and no longer written to
+    Frozen(Bytes),
+}
+
+/// A buffer in WebAssembly memory that Parquet data can be written into directly from JS.
+///
+/// Create one with {@linkcode allocParquetBuffer}, copy the file into {@linkcode
+/// ParquetBuffer.view}, then read it with {@linkcode readParquetBuffer}, {@linkcode
+/// readSchemaBuffer}, {@linkcode readMetadataBuffer}, {@linkcode ParquetReader.fromParquetBuffer}
+/// or {@linkcode ParquetFile.fromParquetBuffer}. Call `free()` once the buffer is no longer needed.
+///
+/// The buffer becomes read-only once it is first read, as readers such as {@linkcode
+/// ParquetReader} keep using its data without copying it.
+#[wasm_bindgen]
+pub struct ParquetBuffer {
+    data: RefCell<BufferData>,
+}
+
+#[wasm_bindgen]
+impl ParquetBuffer {
+    /// The size of the buffer in bytes.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        match &*self.data.borrow() {
+            BufferData::Writable(data) => data.len(),
+            BufferData::Frozen(bytes) => bytes.len(),
+        }
+    }
+
+    /// A `Uint8Array` view into the buffer, to copy Parquet data into.
+    ///
+    /// The view is only valid until the WebAssembly memory grows, which may happen whenever any
+    /// other data is allocated. Fill the view immediately after creating it, e.g. with
+    /// `buffer.view().set(chunk, offset)`, and create a new view for each chunk.
+    ///
+    /// Throws once the buffer has been read. Views created before must not be written to after
+    /// reading the buffer either.
+    #[wasm_bindgen]
+    pub fn view(&self) -> WasmResult<Uint8Array> {
+        match &mut *self.data.borrow_mut() {
+            // Safety: the data is owned by this buffer, and nothing reads it until it is frozen,
+            // after which no more views are created.
+            BufferData::Writable(data) => {
+                Ok(unsafe { Uint8Array::view_mut_raw(data.as_mut_ptr(), data.len()) })
+            }
+            BufferData::Frozen(_) => Err(JsError::new(
+                "ParquetBuffer can no longer be written to once it has been read.",
+            )),
+        }
+    }
+}
+
+impl ParquetBuffer {
+    fn new(data: Vec<u8>) -> Self {
+        Self {
+            data: RefCell::new(BufferData::Writable(data)),
+        }
+    }
+
+    /// Make the buffer read-only, and return its data to share with readers.
+    pub(crate) fn to_bytes(&self) -> WasmResult<Bytes> {
+        let mut data = self.data.borrow_mut();
+        let bytes = match std::mem::replace(&mut *data, BufferData::Frozen(Bytes::new())) {
+            BufferData::Writable(data) => Bytes::from(data),
+            BufferData::Frozen(bytes) => bytes,
+        };
+        *data = BufferData::Frozen(bytes.clone());
+        assert_parquet_file_not_empty(&bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Allocate a zero-filled {@linkcode ParquetBuffer} of `length` bytes in WebAssembly memory.
+///
+/// @param length The size of the Parquet file in bytes
+#[wasm_bindgen(js_name = allocParquetBuffer)]
+pub fn alloc_parquet_buffer(length: usize) -> ParquetBuffer {
+    ParquetBuffer::new(vec![0; length])
+}
+
+impl ParquetInput {
+    /// The Parquet data of this input, copied into WebAssembly memory.
+    pub(crate) fn to_bytes(&self) -> WasmResult<Bytes> {
+        let bytes = if let Some(array) = self.dyn_ref::<Uint8Array>() {
+            Bytes::from(array.to_vec())
+        } else if ArrayBuffer::is_view(self) {
+            // Other typed arrays, including `Uint8Array`s from another realm
+            let get = |name: &str| Reflect::get(self, &name.into()).unwrap_or_default();
+            let view = Uint8Array::new_with_byte_offset_and_length(
+                &get("buffer"),
+                get("byteOffset").as_f64().unwrap_or_default() as u32,
+                get("byteLength").as_f64().unwrap_or_default() as u32,
+            );
+            Bytes::from(view.to_vec())
+        } else if self.is_instance_of::<ArrayBuffer>() {
+            Bytes::from(Uint8Array::new(self).to_vec())
+        } else {
+            return Err(JsError::new("Expected a Uint8Array or an ArrayBuffer."));
+        };
+        assert_parquet_file_not_empty(&bytes)?;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,304 @@
+//! Read files from a byte source implemented in JS.
+//!
+//! A byte source is any object with the size of a file and a method to read a range of it. This
+//! allows reading files from backends that are not supported by this crate, e.g. IndexedDB, the
+//! origin private file system or a gateway that signs each request.
+
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::FutureExt;
+use futures::channel::oneshot;
+use futures::future::BoxFuture;
+use js_sys::{Array, Function, Promise, Reflect};
+use parquet::arrow::arrow_reader::ArrowReaderOptions;
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+
+use crate::abort::{Signal, abortable};
+use crate::coalesce::{IoOptions, coalesce_ranges, coalesce_ranges_batched};
+use crate::common::fetch::cancelled;
+use crate::error::{ParquetWasmError, Result};
+use crate::progress::{Progress, report};
+use crate::utils::{MAX_EXACT_INTEGER, js_bytes, js_error_message};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ByteSource: &'static str = r#"
+export type ByteSource = {
+    /* The size of the file in bytes. */
+    size: number;
+    /* Read the bytes from `start` up to, but excluding, `end`. */
+    getBytes(start: number, end: number): Promise<Uint8Array | ArrayBuffer>;
+    /* Read several byte ranges at once, given as `[start, end]` pairs with an exclusive end. Nearby ranges are coalesced and split according to `coalesceSize` and `maxRequestSize`, and at most `maxConcurrentRequests` ranges are passed to each call. If omitted, `getBytes` is called for each of these ranges instead. */
+    getByteRanges?(ranges: [number, number][]): Promise<(Uint8Array | ArrayBuffer)[]>;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A file that is read with methods implemented in JS
+    #[wasm_bindgen(typescript_type = "ByteSource")]
+    pub type ByteSource;
+}
+
+/// A byte source passed from JS.
+#[derive(Clone, Debug)]
+struct JsByteSource {
+    source: JsValue,
+    size: u64,
+    get_bytes: Function,
+    get_byte_ranges: Option<Function>,
+}
+
+/// Safety: This is not in fact thread-safe. Do not attempt to use this in work-stealing
+/// async runtimes / multi-threaded environments
+///
+/// The source is held by `ByteSourceReader`, which must implement `AsyncFileReader` and so be
+/// `Send`. It is only ever called from local tasks.
+unsafe impl Send for JsByteSource {}
+unsafe impl Sync for JsByteSource {}
+
+impl TryFrom<ByteSource> for JsByteSource {
+    type Error = serde_wasm_bindgen::Error;
+
+    fn try_from(value: ByteSource) -> std::result::Result<Self, Self::Error> {
+        let source: JsValue = value.into();
+        let get = |name: &str| Reflect::get(&source, &name.into()).unwrap_or(JsValue::UNDEFINED);
+
+        let size = get("size")
+            .as_f64()
+            .filter(|size| size.fract() == 0.0 && (0.0..=MAX_EXACT_INTEGER as f64).contains(size))
+            .ok_or_else(|| serde_wasm_bindgen::Error::new("size must be a non-negative integer"))?;
+        let get_bytes = get("getBytes")
+            .dyn_into::<Function>()
+            .map_err(|_| serde_wasm_bindgen::Error::new("getBytes must be a function"))?;
+        let get_byte_ranges = get("getByteRanges");
+        let get_byte_ranges = if get_byte_ranges.is_undefined() || get_byte_ranges.is_null() {
+            None
+        } else {
+            let method = get_byte_ranges
+                .dyn_into::<Function>()
+                .map_err(|_| serde_wasm_bindgen::Error::new("getByteRanges must be a function"))?;
+            Some(method)
+        };
+        Ok(Self {
+            source,
+            size: size as u64,
+            get_bytes,
+            get_byte_ranges,
+        })
+    }
+}
+
+impl JsByteSource {
+    /// Call a method of the source, awaiting the result if it returns a promise.
+    async fn call(&self, name: &str, method: &Function, args: &Array) -> Result<JsValue> {
+        let callback_error = |err: JsValue| {
+            ParquetWasmError::CallbackError(format!("{name}: {}", js_error_message(&err)))
+        };
+        let value = method.apply(&self.source, args).map_err(callback_error)?;
+        match value.dyn_into::<Promise>() {
+            Ok(promise) => JsFuture::from(promise).await.map_err(callback_error),
+            Err(value) => Ok(value),
+        }
+    }
+
+    /// Check that `range` is within the file, so that its bounds are exact JS numbers.
+    fn check_range(&self, range: &Range<u64>) -> Result<()> {
+        if range.start > range.end || range.end > self.size {
+            return Err(ParquetWasmError::PlatformSupportError(format!(
+                "{range:?} is out of bounds of a file of {} bytes",
+                self.size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Convert a value returned for `range` into bytes, checking its length.
+    fn to_bytes(name: &str, value: &JsValue, range: &Range<u64>) -> Result<Bytes> {
+        let bytes = js_bytes(value).ok_or_else(|| {
+            ParquetWasmError::CallbackError(format!(
+                "{name} must return a Uint8Array or an ArrayBuffer"
+            ))
+        })?;
+        if bytes.len() as u64 != range.end - range.start {
+            return Err(ParquetWasmError::CallbackError(format!(
+                "{name} returned {} bytes for {range:?}",
+                bytes.len()
+            )));
+        }
+        Ok(bytes.into())
+    }
+
+    async fn read_range(&self, range: Range<u64>) -> Result<Bytes> {
+        self.check_range(&range)?;
+        let args = Array::of2(&(range.start as f64).into(), &(range.end as f64).into());
+        let value = self.call("getBytes", &self.get_bytes, &args).await?;
+        Self::to_bytes("getBytes", &value, &range)
+    }
+
+    /// Read `ranges` with a single call of `getByteRanges`.
+    async fn read_ranges(&self, ranges: &[Range<u64>]) -> Result<Vec<Bytes>> {
+        let Some(method) = &self.get_byte_ranges else {
+            return Err(ParquetWasmError::CallbackError(
+                "getByteRanges is not defined".to_string(),
+            ));
+        };
+        let pairs = Array::new();
+        for range in ranges {
+            self.check_range(range)?;
+            pairs.push(&Array::of2(
+                &(range.start as f64).into(),
+                &(range.end as f64).into(),
+            ));
+        }
+        let value = self
+            .call("getByteRanges", method, &Array::of1(&pairs))
+            .await?;
+        let values = value
+            .dyn_into::<Array>()
+            .ok()
+            .filter(|values| values.length() as usize == ranges.len())
+            .ok_or_else(|| {
+                ParquetWasmError::CallbackError(
+                    "getByteRanges must return an array with the bytes of each range".to_string(),
+                )
+            })?;
+        ranges
+            .iter()
+            .zip(values.iter())
+            .map(|(range, value)| Self::to_bytes("getByteRanges", &value, range))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ByteSourceReader {
+    source: JsByteSource,
+    io_options: IoOptions,
+    signal: Option<Signal>,
+    progress: Option<Progress>,
+}
+
+impl ByteSourceReader {
+    pub fn try_new(
+        source: ByteSource,
+        coalesce_byte_size: u64,
+    ) -> std::result::Result<Self, serde_wasm_bindgen::Error> {
+        Ok(Self {
+            source: source.try_into()?,
+            io_options: IoOptions::new(coalesce_byte_size),
+            signal: None,
+            progress: None,
+        })
+    }
+
+    /// Coalesce, split and parallelize the calls of `getBytes` and `getByteRanges` according to
+    /// `io_options`.
+    pub fn with_io_options(self, io_options: IoOptions) -> Self {
+        Self { io_options, ..self }
+    }
+
+    /// Fail reads once `signal` is aborted.
+    pub fn with_signal(self, signal: Option<Signal>) -> Self {
+        Self { signal, ..self }
+    }
+
+    /// Report the bytes requested and received to `progress`.
+    pub fn with_progress(self, progress: Option<Progress>) -> Self {
+        Self { progress, ..self }
+    }
+
+    /// Read `length` bytes with `read` in a local task, reporting them to the progress callback.
+    async fn read<T, F, Fut>(&self, length: u64, read: F) -> parquet::errors::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(JsByteSource) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>>,
+    {
+        report(self.progress.as_ref(), |progress| {
+            progress.bytes_requested += length
+        });
+        let (sender, receiver) = oneshot::channel();
+        let source = self.source.clone();
+        let signal = self.signal.clone();
+        spawn_local(async move {
+            let result = match abortable(signal.as_ref(), read(source)).await {
+                Some(result) => result,
+                None => Err(ParquetWasmError::Aborted),
+            };
+            let _ = sender.send(result.map_err(ParquetError::from));
+        });
+        let output = receiver
+            .await
+            .map_err(|err| ParquetError::from(cancelled(err)))??;
+        report(self.progress.as_ref(), |progress| {
+            progress.bytes_received += length
+        });
+        Ok(output)
+    }
+}
+
+impl AsyncFileReader for ByteSourceReader {
+    fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        async move {
+            // Split the range if it exceeds the maximum request size
+            let mut bytes = self.get_byte_ranges(vec![range]).await?;
+            Ok(bytes.pop().unwrap_or_default())
+        }
+        .boxed()
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<u64>>,
+    ) -> BoxFuture<'_, parquet::errors::Result<Vec<Bytes>>> {
+        async move {
+            let reader = &*self;
+            if reader.source.get_byte_ranges.is_some() {
+                return coalesce_ranges_batched(
+                    &ranges,
+                    |requests| {
+                        let length = requests.iter().map(|range| range.end - range.start).sum();
+                        reader.read(length, move |source| async move {
+                            source.read_ranges(&requests).await
+                        })
+                    },
+                    &reader.io_options,
+                )
+                .await;
+            }
+            coalesce_ranges(
+                &ranges,
+                |range| {
+                    reader.read(range.end - range.start, move |source| async move {
+                        source.read_range(range).await
+                    })
+                },
+                &reader.io_options,
+            )
+            .await
+        }
+        .boxed()
+    }
+
+    fn get_metadata<'a>(
+        &'a mut self,
+        _options: Option<&'a ArrowReaderOptions>,
+    ) -> BoxFuture<'a, parquet::errors::Result<Arc<ParquetMetaData>>> {
+        let file_size = self.source.size;
+        async move {
+            let metadata = ParquetMetaDataReader::new()
+                .with_page_index_policy(PageIndexPolicy::Optional)
+                .load_and_finish(self, file_size)
+                .await?;
+            Ok(Arc::new(metadata))
+        }
+        .boxed()
+    }
+}
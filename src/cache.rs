@@ -0,0 +1,206 @@
+//! Cache the byte ranges fetched for a file, so that repeated reads of the same columns do not
+//! fetch them again.
+//!
+//! The cache is shared by all reads of a `ParquetFile` and bounded by the total size of the cached
+//! ranges, evicting the least recently used ranges first.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::ops::Range;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use parquet::errors::ParquetError;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CacheStats: &'static str = r#"
+export type CacheStats = {
+    /* The number of byte ranges read from the cache. */
+    hits: number;
+    /* The number of byte ranges that were not in the cache and had to be fetched. */
+    misses: number;
+    /* The number of bytes currently in the cache. */
+    bytes: number;
+    /* The number of byte ranges currently in the cache. */
+    entries: number;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Statistics of the byte range cache of a file
+    #[wasm_bindgen(typescript_type = "CacheStats")]
+    pub type CacheStats;
+}
+
+/// Statistics of a [`RangeCache`].
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub bytes: u64,
+    pub entries: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    end: u64,
+    data: Bytes,
+    /// The value of the cache's clock when this entry was last read or written
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// The cached ranges, by start offset
+    entries: BTreeMap<u64, Entry>,
+    /// The start offsets of the cached ranges, by the value of the clock when they were last used
+    usage: BTreeMap<u64, u64>,
+    clock: u64,
+    stats: JsCacheStats,
+}
+
+impl CacheState {
+    /// Advance the clock, returning its new value.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+/// A size-bounded least-recently-used cache of byte ranges of a file.
+#[derive(Debug)]
+pub struct RangeCache {
+    /// The maximum number of bytes to cache
+    capacity: u64,
+    state: Mutex<CacheState>,
+}
+
+impl RangeCache {
+    pub fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    /// The bytes of `range`, if it is contained in a cached range.
+    pub fn get(&self, range: &Range<u64>) -> Option<Bytes> {
+        let mut state = self.state.lock().unwrap();
+        let clock = state.tick();
+        let used = state
+            .entries
+            .range_mut(..=range.start)
+            .next_back()
+            .filter(|(_, entry)| entry.end >= range.end)
+            .map(|(start, entry)| {
+                let last_used = std::mem::replace(&mut entry.last_used, clock);
+                let offset = (range.start - start) as usize;
+                let bytes = entry
+                    .data
+                    .slice(offset..offset + (range.end - range.start) as usize);
+                (*start, last_used, bytes)
+            });
+        match used {
+            Some((start, last_used, bytes)) => {
+                state.usage.remove(&last_used);
+                state.usage.insert(clock, start);
+                state.stats.hits += 1;
+                Some(bytes)
+            }
+            None => {
+                state.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Cache the bytes of `range`, evicting the least recently used ranges if the cache is full.
+    ///
+    /// Ranges larger than the capacity of the cache are not cached.
+    pub fn insert(&self, range: Range<u64>, data: Bytes) {
+        let size = data.len() as u64;
+        if size == 0 || size > self.capacity {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let clock = state.tick();
+        let entry = Entry {
+            end: range.end,
+            data,
+            last_used: clock,
+        };
+        if let Some(replaced) = state.entries.insert(range.start, entry) {
+            state.usage.remove(&replaced.last_used);
+            state.stats.bytes -= replaced.data.len() as u64;
+        }
+        state.usage.insert(clock, range.start);
+        state.stats.bytes += size;
+
+        while state.stats.bytes > self.capacity {
+            let Some((_, oldest)) = state.usage.pop_first() else {
+                break;
+            };
+            if let Some(evicted) = state.entries.remove(&oldest) {
+                state.stats.bytes -= evicted.data.len() as u64;
+            }
+        }
+        state.stats.entries = state.entries.len();
+    }
+
+    /// Remove all cached ranges. The hit and miss counters are kept.
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.entries.clear();
+        state.usage.clear();
+        state.stats.bytes = 0;
+        state.stats.entries = 0;
+    }
+
+    pub fn stats(&self) -> JsCacheStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+}
+
+/// Fetch the `ranges` missing from `cached`, the cached bytes of each range, calling `insert`
+/// with every fetched range, and return the bytes of all `ranges`.
+///
+/// Errors if `fetch` does not return the bytes of every missing range.
+pub(crate) async fn fetch_missing<F, Fut>(
+    ranges: Vec<Range<u64>>,
+    mut cached: Vec<Option<Bytes>>,
+    fetch: F,
+    mut insert: impl FnMut(Range<u64>, &Bytes),
+) -> parquet::errors::Result<Vec<Bytes>>
+where
+    F: FnOnce(Vec<Range<u64>>) -> Fut,
+    Fut: Future<Output = parquet::errors::Result<Vec<Bytes>>>,
+{
+    let missing = ranges
+        .into_iter()
+        .zip(&cached)
+        .filter(|(_, bytes)| bytes.is_none())
+        .map(|(range, _)| range)
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        let fetched = fetch(missing.clone()).await?;
+        if fetched.len() != missing.len() {
+            return Err(ParquetError::General(format!(
+                "Expected {} byte ranges but received {}",
+                missing.len(),
+                fetched.len()
+            )));
+        }
+        let mut fetched = missing.into_iter().zip(fetched);
+        for slot in cached.iter_mut().filter(|bytes| bytes.is_none()) {
+            if let Some((range, bytes)) = fetched.next() {
+                insert(range, &bytes);
+                *slot = Some(bytes);
+            }
+        }
+    }
+    Ok(cached.into_iter().flatten().collect())
+}
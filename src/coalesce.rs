@@ -0,0 +1,167 @@
+//! Coalesce, split and parallelize the range requests of async readers.
+//!
+//! Like [`object_store::coalesce_ranges`], ranges separated by small gaps are fetched with a single
+//! request. In addition, requests are limited to a maximum size, by not coalescing ranges past it
+//! and splitting larger ranges into several requests, and the number of requests in flight is
+//! configurable.
+
+use std::future::Future;
+use std::ops::Range;
+
+use bytes::{Bytes, BytesMut};
+use futures::{StreamExt, TryStreamExt, stream};
+
+/// The default number of concurrent requests made to fetch the ranges of a single read.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 10;
+
+/// How an async reader fetches byte ranges.
+#[derive(Clone, Copy, Debug)]
+pub struct IoOptions {
+    /// Ranges separated by a gap of at most this many bytes are fetched with a single request.
+    pub coalesce_size: u64,
+
+    /// The maximum number of bytes fetched by a single request.
+    pub max_request_size: Option<u64>,
+
+    /// The maximum number of concurrent requests made to fetch the ranges of a single read.
+    pub max_concurrent_requests: usize,
+}
+
+impl IoOptions {
+    pub fn new(coalesce_size: u64) -> Self {
+        Self {
+            coalesce_size,
+            max_request_size: None,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+}
+
+/// Fetch `ranges` with the requests described by `options`, returning the bytes of each range.
+pub async fn coalesce_ranges<F, Fut, E>(
+    ranges: &[Range<u64>],
+    fetch: F,
+    options: &IoOptions,
+) -> Result<Vec<Bytes>, E>
+where
+    F: FnMut(Range<u64>) -> Fut,
+    Fut: Future<Output = Result<Bytes, E>>,
+{
+    let max_concurrent_requests = options.max_concurrent_requests.max(1);
+    fetch_requests(ranges, options, |requests| {
+        stream::iter(requests)
+            .map(fetch)
+            .buffered(max_concurrent_requests)
+            .try_collect::<Vec<_>>()
+    })
+    .await
+}
+
+/// Fetch `ranges` with the requests described by `options`, passing up to
+/// `max_concurrent_requests` requests at a time to a single call of `fetch`.
+pub async fn coalesce_ranges_batched<F, Fut, E>(
+    ranges: &[Range<u64>],
+    mut fetch: F,
+    options: &IoOptions,
+) -> Result<Vec<Bytes>, E>
+where
+    F: FnMut(Vec<Range<u64>>) -> Fut,
+    Fut: Future<Output = Result<Vec<Bytes>, E>>,
+{
+    let batch_size = options.max_concurrent_requests.max(1);
+    fetch_requests(ranges, options, |requests| async move {
+        let mut chunks = Vec::with_capacity(requests.len());
+        for batch in requests.chunks(batch_size) {
+            chunks.extend(fetch(batch.to_vec()).await?);
+        }
+        Ok::<_, E>(chunks)
+    })
+    .await
+}
+
+/// Fetch `ranges` by passing the requests described by `options` to `fetch_all`, which returns
+/// the bytes of each request in order.
+async fn fetch_requests<F, Fut, E>(
+    ranges: &[Range<u64>],
+    options: &IoOptions,
+    fetch_all: F,
+) -> Result<Vec<Bytes>, E>
+where
+    F: FnOnce(Vec<Range<u64>>) -> Fut,
+    Fut: Future<Output = Result<Vec<Bytes>, E>>,
+{
+    let fetch_ranges = merge_ranges(ranges, options.coalesce_size, options.max_request_size);
+    let requests = fetch_ranges
+        .iter()
+        .flat_map(|range| split_range(range.clone(), options.max_request_size))
+        .collect::<Vec<_>>();
+    let mut chunks = fetch_all(requests).await?.into_iter();
+
+    // Reassemble the ranges that were split into several requests
+    let fetched = fetch_ranges
+        .iter()
+        .map(|range| {
+            let num_chunks = split_range(range.clone(), options.max_request_size).len();
+            if num_chunks == 1 {
+                return chunks.next().unwrap_or_default();
+            }
+            let mut bytes = BytesMut::with_capacity((range.end - range.start) as usize);
+            for chunk in chunks.by_ref().take(num_chunks) {
+                bytes.extend_from_slice(&chunk);
+            }
+            bytes.freeze()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ranges
+        .iter()
+        .map(|range| {
+            let idx = fetch_ranges.partition_point(|v| v.start <= range.start) - 1;
+            let fetch_range = &fetch_ranges[idx];
+            let fetch_bytes = &fetched[idx];
+
+            let start = range.start - fetch_range.start;
+            let end = range.end - fetch_range.start;
+            let range = (start as usize)..(end as usize).min(fetch_bytes.len());
+            fetch_bytes.slice(range)
+        })
+        .collect())
+}
+
+/// Returns a sorted list of ranges that cover `ranges`, merging ranges separated by at most
+/// `coalesce` bytes unless the merged range would exceed `max_size`.
+fn merge_ranges(ranges: &[Range<u64>], coalesce: u64, max_size: Option<u64>) -> Vec<Range<u64>> {
+    let mut ranges = ranges.to_vec();
+    ranges.sort_unstable_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<u64>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if let Some(last) = merged.last_mut() {
+            // Overlapping ranges are always merged
+            let overlaps = range.start <= last.end;
+            let fits = max_size.is_none_or(|max| range.end.max(last.end) - last.start <= max);
+            if overlaps || (range.start - last.end <= coalesce && fits) {
+                last.end = last.end.max(range.end);
+                continue;
+            }
+        }
+        merged.push(range);
+    }
+    merged
+}
+
+/// Split `range` into requests of at most `max_size` bytes.
+fn split_range(range: Range<u64>, max_size: Option<u64>) -> Vec<Range<u64>> {
+    let max_size = match max_size {
+        Some(max_size) if range.end - range.start > max_size => max_size.max(1),
+        _ => return vec![range],
+    };
+    let mut requests = vec![];
+    let mut start = range.start;
+    while start < range.end {
+        let end = (start + max_size).min(range.end);
+        requests.push(start..end);
+        start = end;
+    }
+    requests
+}
@@ -1,24 +1,38 @@
 use futures::channel::oneshot;
 use futures::future::BoxFuture;
 use range_reader::{RangeOutput, RangedAsyncReader};
-use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
+use crate::error::{ParquetWasmError, Result};
+
 /// Get content-length of file
-pub async fn _get_content_length(url: String) -> Result<usize, reqwest::Error> {
+pub async fn _get_content_length(url: String) -> Result<usize> {
     let client = reqwest::Client::new();
-    let resp = client.head(url).send().await?;
-    Ok(resp.content_length().unwrap().try_into().unwrap())
+    let resp = client.head(url).send().await?.error_for_status()?;
+    let content_length = resp.content_length().ok_or_else(|| {
+        ParquetWasmError::PlatformSupportError(
+            "Response does not have a Content-Length header".to_string(),
+        )
+    })?;
+    content_length.try_into().map_err(|_| {
+        ParquetWasmError::PlatformSupportError(format!(
+            "Content length {content_length} is too large"
+        ))
+    })
 }
 
-pub async fn get_content_length(url: String) -> Result<usize, reqwest::Error> {
-    let (sender, receiver) = oneshot::channel::<usize>();
+pub async fn get_content_length(url: String) -> Result<usize> {
+    let (sender, receiver) = oneshot::channel();
     spawn_local(async move {
-        let inner_data = _get_content_length(url).await.unwrap();
-        sender.send(inner_data).unwrap();
+        // The receiver is only dropped if the caller is no longer waiting for the result
+        let _ = sender.send(_get_content_length(url).await);
     });
-    let data = receiver.await.unwrap();
-    Ok(data)
+    receiver.await.map_err(cancelled)?
+}
+
+/// The error of a request whose task ended without sending its result.
+pub fn cancelled(_: oneshot::Canceled) -> ParquetWasmError {
+    ParquetWasmError::PlatformSupportError("The request was cancelled".to_string())
 }
 
 /// Construct range header from start and length
@@ -42,7 +56,7 @@ async fn _make_range_request(
     url: &str,
     start: u64,
     length: usize,
-) -> Result<Vec<u8>, reqwest::Error> {
+) -> std::result::Result<Vec<u8>, reqwest::Error> {
     let client = reqwest::Client::new();
     let range_str = range_from_start_and_length(start, length as u64);
     let resp = client
@@ -54,18 +68,12 @@ async fn _make_range_request(
     Ok(resp.bytes().await?.to_vec())
 }
 
-pub async fn make_range_request(
-    url: String,
-    start: u64,
-    length: usize,
-) -> Result<Vec<u8>, JsValue> {
-    let (sender, receiver) = oneshot::channel::<Vec<u8>>();
+pub async fn make_range_request(url: String, start: u64, length: usize) -> Result<Vec<u8>> {
+    let (sender, receiver) = oneshot::channel();
     spawn_local(async move {
-        let inner_data = _make_range_request(&url, start, length).await.unwrap();
-        sender.send(inner_data).unwrap();
+        let _ = sender.send(_make_range_request(&url, start, length).await);
     });
-    let data = receiver.await.unwrap();
-    Ok(data)
+    Ok(receiver.await.map_err(cancelled)??)
 }
 
 /// Create a RangedAsyncReader
@@ -84,7 +92,7 @@ pub fn create_reader(
         Box::pin(async move {
             let data = make_range_request(url.clone(), start, length)
                 .await
-                .unwrap();
+                .map_err(|err| std::io::Error::other(err.to_string()))?;
             Ok(RangeOutput { start, data })
         }) as BoxFuture<'static, std::io::Result<RangeOutput>>
     });
@@ -0,0 +1,626 @@
+//! Read a collection of Parquet files with possibly differing schemas as a single table.
+//!
+//! Files may be Hive-partitioned, i.e. have `key=value` directory names in their paths. The
+//! partition values are added to each file's batches as constant columns, and filters on partition
+//! columns skip whole files. A column that is stored in the files is read from the files, even if
+//! their paths also have a partition value for it.
+
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, LargeListArray, ListArray, RecordBatchOptions, StructArray,
+    new_null_array,
+};
+use arrow::compute::cast;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch as ArrowRecordBatch;
+use arrow_schema::{DataType, FieldRef, Fields, Schema, SchemaRef, TimeUnit};
+use arrow_wasm::{RecordBatch, Table};
+use futures::stream::LocalBoxStream;
+use futures::{StreamExt, TryStreamExt, stream};
+use js_sys::Reflect;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::abort::abort_error;
+use crate::error::{ParquetWasmError, Result, WasmResult};
+use crate::file_options::JsParquetFileOptions;
+use crate::filter::FilterExpression;
+use crate::partition::Partitions;
+use crate::read_options::{JsReaderOptions, ReaderOptions};
+use crate::reader_async::{ParquetFile, apply_offset_limit};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_DatasetOptions: &'static str = r#"
+export type DatasetOptions = {
+    /* Read partition columns from `key=value` directory names in the file paths. Defaults to true. */
+    hivePartitioning?: boolean;
+    /* Only open the files whose partition values may match this filter. Conditions on other columns are ignored. */
+    filter?: FilterExpression;
+    /* The number of bytes at the end of each remote file to fetch with the first request, to open files with a single request. See {@linkcode ParquetFileOptions}. */
+    footerPrefetchBytes?: number;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Dataset options
+    #[wasm_bindgen(typescript_type = "DatasetOptions")]
+    pub type DatasetOptions;
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsDatasetOptions {
+    /// Read partition columns from `key=value` directory names in the file paths.
+    pub hive_partitioning: Option<bool>,
+
+    /// Only open the files whose partition values may match this filter.
+    pub filter: Option<FilterExpression>,
+
+    /// The number of bytes at the end of each remote file to fetch with the first request.
+    pub footer_prefetch_bytes: Option<usize>,
+}
+
+impl JsDatasetOptions {
+    /// Parse the partitions of the files at `paths`, and select the `sources` of the files
+    /// matching the filter.
+    fn select_files<T>(&self, sources: Vec<T>, paths: &[String]) -> Result<(Vec<T>, Partitions)> {
+        let partitions = if self.hive_partitioning.unwrap_or(true) {
+            Partitions::from_paths(paths)?
+        } else {
+            Partitions::empty(paths.len())
+        };
+        let Some(filter) = &self.filter else {
+            return Ok((sources, partitions));
+        };
+
+        let indices = partitions.matching_files(filter)?;
+        let sources = sources
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| indices.binary_search(i).is_ok())
+            .map(|(_, source)| source)
+            .collect();
+        Ok((sources, partitions.select(&indices)?))
+    }
+}
+
+impl TryFrom<DatasetOptions> for JsDatasetOptions {
+    type Error = serde_wasm_bindgen::Error;
+
+    fn try_from(value: DatasetOptions) -> std::result::Result<Self, Self::Error> {
+        serde_wasm_bindgen::from_value(value.obj)
+    }
+}
+
+/// The number of files whose metadata is fetched concurrently when opening a dataset
+const OPEN_CONCURRENCY: usize = 16;
+
+/// A collection of Parquet files that are read as a single table.
+///
+/// The schemas of the files are unified: columns that are missing from some files are filled
+/// with nulls, and columns with different but compatible types (e.g. `Int32` and `Int64`) are
+/// widened to a common type. Partition columns are added after the columns of the files, unless
+/// the files contain a column of the same name, whose values are read instead.
+#[wasm_bindgen]
+pub struct ParquetDataset {
+    files: Vec<ParquetFile>,
+    partitions: Partitions,
+    schema: SchemaRef,
+}
+
+#[wasm_bindgen]
+impl ParquetDataset {
+    /// Construct a ParquetDataset from a list of URLs.
+    ///
+    /// The metadata of all files is fetched when the dataset is constructed, except for files
+    /// excluded by the `filter` option.
+    ///
+    /// @param urls The URLs of the files, e.g. `https://example.com/data/year=2024/part-0.parquet`
+    /// @param options See {@linkcode DatasetOptions}
+    #[wasm_bindgen(js_name = fromUrls)]
+    pub async fn from_urls(
+        urls: Vec<String>,
+        options: Option<DatasetOptions>,
+    ) -> WasmResult<ParquetDataset> {
+        let options: JsDatasetOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let (urls, partitions) = options.select_files(urls.clone(), &urls)?;
+        let file_options = JsParquetFileOptions {
+            footer_prefetch_bytes: options.footer_prefetch_bytes,
+            ..Default::default()
+        };
+        let files = stream::iter(
+            urls.into_iter()
+                .map(|url| ParquetFile::open_url(url, file_options.clone())),
+        )
+        .buffered(OPEN_CONCURRENCY)
+        .try_collect()
+        .await?;
+        Ok(Self::try_new(files, partitions)?)
+    }
+
+    /// Construct a ParquetDataset from a list of [Blob] or [File] handles.
+    ///
+    /// To read the files of a `FileList`, e.g. from a file input or a drop event, pass
+    /// `Array.from(fileList)`. Partition values are parsed from the `webkitRelativePath` of files
+    /// picked from a directory input, or else from the file name.
+    ///
+    /// [Blob]: https://developer.mozilla.org/en-US/docs/Web/API/Blob
+    /// [File]: https://developer.mozilla.org/en-US/docs/Web/API/File
+    ///
+    /// Safety: Do not use this in a multi-threaded environment,
+    /// (transitively depends on `!Send` `web_sys::Blob`)
+    #[wasm_bindgen(js_name = fromFiles)]
+    pub async fn from_files(
+        handles: Vec<web_sys::Blob>,
+        options: Option<DatasetOptions>,
+    ) -> WasmResult<ParquetDataset> {
+        let options: JsDatasetOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let paths = handles.iter().map(blob_path).collect::<Vec<_>>();
+        let (handles, partitions) = options.select_files(handles, &paths)?;
+        let files = stream::iter(
+            handles
+                .into_iter()
+                .map(|handle| ParquetFile::from_file(handle, None)),
+        )
+        .buffered(OPEN_CONCURRENCY)
+        .try_collect()
+        .await?;
+        Ok(Self::try_new(files, partitions)?)
+    }
+
+    /// The number of files in the dataset.
+    #[wasm_bindgen(getter, js_name = numFiles)]
+    pub fn num_files(&self) -> usize {
+        self.files.len()
+    }
+
+    /// The unified schema of all files in the dataset.
+    #[wasm_bindgen]
+    pub fn schema(&self) -> WasmResult<arrow_wasm::Schema> {
+        Ok(self.schema.clone().into())
+    }
+
+    /// Read the dataset into a single table.
+    ///
+    /// @param options
+    ///
+    ///    Options for reading Parquet data. Optional keys include:
+    ///
+    ///    - `batchSize`: The number of rows in each batch. If not provided, the upstream parquet
+    ///           default is 1024.
+    ///    - `limit`: Provide a limit to the number of rows to be read.
+    ///    - `offset`: Provide an offset to skip over the given number of rows.
+    ///    - `columns`: The column names to read. Each column must exist in at least one file or
+    ///           be a partition column.
+    ///    - `filter`: Only read rows matching this {@linkcode FilterExpression}. Columns that are
+    ///           missing from a file are treated as null. Files whose partition values do not
+    ///           match are skipped.
+    ///    - `typeCoercion`: Convert the Arrow types read from the file. See {@linkcode TypeCoercion}.
+    ///    - `schema`: Read columns as the types of the fields with the same name in this schema,
+    ///           given as an Arrow IPC stream or a {@linkcode SchemaDescription}.
+    ///    - `skipArrowMetadata`: Ignore the Arrow schema stored in the file by the writer.
+    ///    - `rowNumberColumn`: Append a column with this name holding the 0-based position of each
+    ///           row in the file.
+    ///    - `concurrency`: The number of concurrent requests to make per file
+    ///    - `signal`: An `AbortSignal` to cancel the read with.
+    ///    - `onProgress`: A callback receiving the {@linkcode ReadProgress} of all files together.
+    ///
+    ///    `rowGroups` is not supported, as row group indexes differ between files.
+    #[wasm_bindgen]
+    pub async fn read(&self, options: Option<ReaderOptions>) -> WasmResult<Table> {
+        let options: JsReaderOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let signal = options.signal.as_ref();
+        let (schema, stream) = self.record_batch_stream(&options)?;
+        let batches = stream
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|err| abort_error(signal, err))?;
+        Ok(Table::new(schema, batches))
+    }
+
+    /// Create a readable stream of record batches over all files in the dataset, in order.
+    ///
+    /// Each item in the stream will be a {@linkcode RecordBatch}.
+    ///
+    /// @param options Options for reading Parquet data. See {@linkcode ParquetDataset.read} for the
+    ///     supported keys.
+    #[wasm_bindgen]
+    pub async fn stream(
+        &self,
+        options: Option<ReaderOptions>,
+    ) -> WasmResult<wasm_streams::readable::sys::ReadableStream> {
+        let options: JsReaderOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let (_schema, stream) = self.record_batch_stream(&options)?;
+        let signal = options.signal;
+        let out_stream = stream.map(move |maybe_record_batch| match maybe_record_batch {
+            Ok(record_batch) => Ok(RecordBatch::new(record_batch).into()),
+            Err(err) => Err(JsError::from(abort_error(signal.as_ref(), err)).into()),
+        });
+        Ok(wasm_streams::ReadableStream::from_stream(out_stream).into_raw())
+    }
+}
+
+impl ParquetDataset {
+    fn try_new(files: Vec<ParquetFile>, partitions: Partitions) -> Result<Self> {
+        let schemas = files
+            .iter()
+            .map(|file| file.output_schema(&Default::default()))
+            .collect::<Result<Vec<_>>>()?;
+        let file_schema = unify_schemas(&schemas)?;
+        // Values stored in the files take precedence over the values parsed from their paths
+        let partitions = partitions.without_columns(file_schema.fields())?;
+        let schema = with_partition_fields(&file_schema, partitions.fields());
+        Ok(Self {
+            files,
+            partitions,
+            schema,
+        })
+    }
+
+    /// Read the batches selected by `options` from all files, converted to a common schema.
+    fn record_batch_stream(
+        &self,
+        options: &JsReaderOptions,
+    ) -> Result<(SchemaRef, LocalBoxStream<'static, Result<ArrowRecordBatch>>)> {
+        if options.row_groups.is_some() {
+            return Err(ArrowError::InvalidArgumentError(
+                "rowGroups is not supported when reading a dataset".to_string(),
+            )
+            .into());
+        }
+        if let Some(columns) = &options.columns {
+            if let Some(column) = columns.iter().find(|column| {
+                !self.partitions.is_partition_column(column)
+                    && !self.files.iter().any(|file| file.has_column(column))
+            }) {
+                return Err(ParquetWasmError::UnknownColumn(column.clone()));
+            }
+        }
+
+        let file_options = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(file_index, file)| self.file_options(file_index, file, options))
+            .collect::<Result<Vec<_>>>()?;
+        let schemas = self
+            .files
+            .iter()
+            .zip(&file_options)
+            .map(|(file, file_options)| file.output_schema(file_options))
+            .collect::<Result<Vec<_>>>()?;
+        let partition_fields = self
+            .partitions
+            .fields()
+            .iter()
+            .filter(|field| {
+                options
+                    .columns
+                    .as_ref()
+                    .is_none_or(|columns| columns.contains(field.name()))
+            })
+            .cloned()
+            .collect::<Fields>();
+        let schema = with_partition_fields(&unify_schemas(&schemas)?, &partition_fields);
+
+        // Skip the files whose partition values do not match the filter
+        let file_indices = match &options.filter {
+            Some(filter) => self.partitions.matching_files(filter)?,
+            None => (0..self.files.len()).collect(),
+        };
+        let files = file_indices
+            .into_iter()
+            .map(|file_index| {
+                (
+                    file_index,
+                    self.files[file_index].clone(),
+                    file_options[file_index].clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let partitions = self.partitions.clone();
+        let batch_schema = schema.clone();
+        let batches = stream::iter(files)
+            .then(|(file_index, file, file_options)| async move {
+                let batches = file.record_batch_stream(file_options).await?;
+                Ok::<_, ParquetWasmError>(batches.map_ok(move |batch| (file_index, batch)))
+            })
+            .try_flatten()
+            .map(move |maybe_batch| {
+                let (file_index, batch) = maybe_batch?;
+                let batch = partitions.append_columns(file_index, batch, &partition_fields)?;
+                Ok::<_, ParquetWasmError>(conform_batch(batch, &batch_schema)?)
+            });
+
+        let batches = apply_offset_limit(batches, options.offset, options.limit);
+        Ok((schema, batches))
+    }
+
+    /// The options to read a single file of the dataset with.
+    fn file_options(
+        &self,
+        file_index: usize,
+        file: &ParquetFile,
+        options: &JsReaderOptions,
+    ) -> Result<JsReaderOptions> {
+        let has_column = |column: &str| file.has_column(column);
+        let filter = options
+            .filter
+            .as_ref()
+            .map(|filter| {
+                let filter = self.partitions.file_filter(file_index, filter)?;
+                Ok::<_, ArrowError>(filter.with_missing_columns(&has_column))
+            })
+            .transpose()?;
+        Ok(JsReaderOptions {
+            columns: options.columns.as_ref().map(|columns| {
+                columns
+                    .iter()
+                    .filter(|column| has_column(column))
+                    .cloned()
+                    .collect()
+            }),
+            filter,
+            // The offset applies to the dataset as a whole, but no file needs to produce more
+            // rows than the offset plus the limit
+            offset: None,
+            limit: options
+                .limit
+                .map(|limit| limit.saturating_add(options.offset.unwrap_or(0))),
+            ..options.clone()
+        })
+    }
+}
+
+/// The path of a file to parse partition values from.
+fn blob_path(blob: &web_sys::Blob) -> String {
+    // Files picked from a directory input have a path relative to that directory
+    let relative_path = Reflect::get(blob, &"webkitRelativePath".into())
+        .ok()
+        .and_then(|path| path.as_string())
+        .filter(|path| !path.is_empty());
+    relative_path
+        .or_else(|| blob.dyn_ref::<web_sys::File>().map(|file| file.name()))
+        .unwrap_or_default()
+}
+
+/// Append partition columns to the unified schema of the files.
+fn with_partition_fields(schema: &SchemaRef, partition_fields: &Fields) -> SchemaRef {
+    if partition_fields.is_empty() {
+        return schema.clone();
+    }
+    let fields = schema
+        .fields()
+        .iter()
+        .chain(partition_fields.iter())
+        .cloned()
+        .collect::<Fields>();
+    Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+}
+
+/// Unify the schemas of multiple files into a single schema.
+///
+/// Fields are ordered by their first appearance. Fields that are missing from some schemas
+/// become nullable, and fields with differing types are widened with [`merge_data_types`].
+fn unify_schemas(schemas: &[SchemaRef]) -> Result<SchemaRef> {
+    let Some((first, rest)) = schemas.split_first() else {
+        return Ok(Arc::new(Schema::empty()));
+    };
+    let fields = rest
+        .iter()
+        .try_fold(first.fields().clone(), |fields, schema| {
+            merge_fields(&fields, schema.fields())
+        })?;
+    Ok(Arc::new(Schema::new_with_metadata(
+        fields,
+        first.metadata().clone(),
+    )))
+}
+
+fn merge_fields(left: &Fields, right: &Fields) -> std::result::Result<Fields, ArrowError> {
+    let mut fields = left
+        .iter()
+        .map(|field| match right.find(field.name()) {
+            Some((_, other)) => merge_field(field, other),
+            None => Ok(Arc::new(field.as_ref().clone().with_nullable(true))),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    fields.extend(
+        right
+            .iter()
+            .filter(|field| left.find(field.name()).is_none())
+            .map(|field| Arc::new(field.as_ref().clone().with_nullable(true))),
+    );
+    Ok(fields.into())
+}
+
+fn merge_field(left: &FieldRef, right: &FieldRef) -> std::result::Result<FieldRef, ArrowError> {
+    let data_type = merge_data_types(left.data_type(), right.data_type()).ok_or_else(|| {
+        ArrowError::SchemaError(format!(
+            "Cannot unify types {} and {} of column {}",
+            left.data_type(),
+            right.data_type(),
+            left.name()
+        ))
+    })?;
+    let nullable = left.is_nullable() || right.is_nullable();
+    if &data_type == left.data_type() && nullable == left.is_nullable() {
+        return Ok(left.clone());
+    }
+    Ok(Arc::new(
+        left.as_ref()
+            .clone()
+            .with_data_type(data_type)
+            .with_nullable(nullable),
+    ))
+}
+
+/// The narrowest type that both data types can be cast to without losing information, if any.
+///
+/// 64-bit integers are not merged with floating point types, as Float64 cannot represent
+/// integers beyond 2^53 exactly.
+fn merge_data_types(left: &DataType, right: &DataType) -> Option<DataType> {
+    if left == right {
+        return Some(left.clone());
+    }
+    match (left, right) {
+        (DataType::Null, other) | (other, DataType::Null) => Some(other.clone()),
+        (DataType::Utf8, DataType::LargeUtf8) | (DataType::LargeUtf8, DataType::Utf8) => {
+            Some(DataType::LargeUtf8)
+        }
+        (DataType::Binary, DataType::LargeBinary) | (DataType::LargeBinary, DataType::Binary) => {
+            Some(DataType::LargeBinary)
+        }
+        (DataType::Timestamp(left_unit, left_tz), DataType::Timestamp(right_unit, right_tz))
+            if left_tz == right_tz =>
+        {
+            let unit = if time_unit_rank(left_unit) >= time_unit_rank(right_unit) {
+                left_unit
+            } else {
+                right_unit
+            };
+            Some(DataType::Timestamp(*unit, left_tz.clone()))
+        }
+        (DataType::Struct(left_fields), DataType::Struct(right_fields)) => Some(DataType::Struct(
+            merge_fields(left_fields, right_fields).ok()?,
+        )),
+        (DataType::List(left_field), DataType::List(right_field)) => {
+            Some(DataType::List(merge_field(left_field, right_field).ok()?))
+        }
+        (DataType::LargeList(left_field), DataType::LargeList(right_field)) => Some(
+            DataType::LargeList(merge_field(left_field, right_field).ok()?),
+        ),
+        _ => merge_numeric_types(left, right),
+    }
+}
+
+fn merge_numeric_types(left: &DataType, right: &DataType) -> Option<DataType> {
+    let is_int_or_float = |t: &DataType| t.is_integer() || t.is_floating();
+    if !is_int_or_float(left) || !is_int_or_float(right) {
+        return None;
+    }
+    if left.is_floating() || right.is_floating() {
+        // Float64 holds all integers of up to 32 bits exactly, but not all 64-bit integers
+        let is_64_bit_integer = |t: &DataType| t.is_integer() && t.primitive_width() == Some(8);
+        if is_64_bit_integer(left) || is_64_bit_integer(right) {
+            return None;
+        }
+        return Some(DataType::Float64);
+    }
+
+    // Both are integers
+    let left_width = left.primitive_width()?;
+    let right_width = right.primitive_width()?;
+    let wider = if left_width >= right_width {
+        left
+    } else {
+        right
+    };
+    match (left.is_signed_integer(), right.is_signed_integer()) {
+        (true, true) | (false, false) => Some(wider.clone()),
+        // A signed integer can only hold all values of a narrower unsigned integer
+        _ => {
+            let unsigned_width = if left.is_signed_integer() {
+                right_width
+            } else {
+                left_width
+            };
+            match left_width.max(right_width).max(unsigned_width * 2) {
+                1 => Some(DataType::Int8),
+                2 => Some(DataType::Int16),
+                4 => Some(DataType::Int32),
+                8 => Some(DataType::Int64),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn time_unit_rank(unit: &TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
+    }
+}
+
+/// Convert a batch read from a single file to the unified schema of the dataset.
+fn conform_batch(
+    batch: ArrowRecordBatch,
+    schema: &SchemaRef,
+) -> std::result::Result<ArrowRecordBatch, ArrowError> {
+    if batch.schema_ref() == schema {
+        return Ok(batch);
+    }
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.column_by_name(field.name()) {
+            Some(column) => conform_array(column, field.data_type()),
+            None => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    ArrowRecordBatch::try_new_with_options(schema.clone(), columns, &options)
+}
+
+fn conform_array(
+    array: &ArrayRef,
+    data_type: &DataType,
+) -> std::result::Result<ArrayRef, ArrowError> {
+    if array.data_type() == data_type {
+        return Ok(array.clone());
+    }
+    match (array.data_type(), data_type) {
+        (DataType::Struct(_), DataType::Struct(fields)) => {
+            let struct_array = array.as_struct();
+            let columns = fields
+                .iter()
+                .map(|field| match struct_array.column_by_name(field.name()) {
+                    Some(column) => conform_array(column, field.data_type()),
+                    None => Ok(new_null_array(field.data_type(), array.len())),
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok(Arc::new(StructArray::try_new(
+                fields.clone(),
+                columns,
+                struct_array.nulls().cloned(),
+            )?))
+        }
+        (DataType::List(_), DataType::List(field)) => {
+            let list_array = array.as_list::<i32>();
+            Ok(Arc::new(ListArray::try_new(
+                field.clone(),
+                list_array.offsets().clone(),
+                conform_array(list_array.values(), field.data_type())?,
+                list_array.nulls().cloned(),
+            )?))
+        }
+        (DataType::LargeList(_), DataType::LargeList(field)) => {
+            let list_array = array.as_list::<i64>();
+            Ok(Arc::new(LargeListArray::try_new(
+                field.clone(),
+                list_array.offsets().clone(),
+                conform_array(list_array.values(), field.data_type())?,
+                list_array.nulls().cloned(),
+            )?))
+        }
+        _ => cast(array, data_type),
+    }
+}
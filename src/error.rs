@@ -17,8 +17,12 @@ pub enum ParquetWasmError {
     HTTPError(Box<reqwest::Error>),
     #[error("Platform error: `{0}`")]
     PlatformSupportError(String),
+    #[error("Callback error: `{0}`")]
+    CallbackError(String),
     #[error("Dyn casting error")]
     DynCastingError(JsValue),
+    #[error("The operation was aborted")]
+    Aborted,
 }
 
 pub type Result<T> = std::result::Result<T, ParquetWasmError>;
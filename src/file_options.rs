@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::abort::Signal;
+use crate::coalesce::{DEFAULT_MAX_CONCURRENT_REQUESTS, IoOptions};
+use crate::error::{ParquetWasmError, Result};
+use crate::persistent_cache::CacheAdapter;
+use crate::retry::RetryOptions;
+use crate::utils::js_error_message;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ParquetFileOptions: &'static str = r#"
+export type ParquetFileOptions = {
+    /* Cancel opening the file when this signal is aborted. In-flight requests are aborted and the promise rejects with an abort error. */
+    signal?: AbortSignal;
+    /* Headers to send with every request for the file, e.g. `{ Authorization: "Bearer <token>" }`. */
+    headers?: Record<string, string>;
+    /* The credentials mode of every request for the file. See https://developer.mozilla.org/en-US/docs/Web/API/Request/credentials */
+    credentials?: "omit" | "same-origin" | "include";
+    /* Called before every request for the file, to refresh an expiring token or presigned URL. The returned headers are sent in addition to `headers`, replacing headers with the same name. */
+    prepareRequest?: (request: FileRequest) => RequestOverrides | undefined | Promise<RequestOverrides | undefined>;
+    /* How to retry requests that fail with network errors or `408`, `429` and `5xx` responses. Requests are attempted 3 times by default. */
+    retry?: RetryOptions;
+    /* Byte ranges separated by a gap of at most this many bytes are fetched with a single request. Defaults to 1 MiB for URLs and byte sources and 1024 for Blobs. */
+    coalesceSize?: number;
+    /* The maximum number of bytes fetched by a single request. Larger ranges are split into several requests. Unlimited by default. */
+    maxRequestSize?: number;
+    /* The maximum number of concurrent requests made to fetch the column chunks of a row group. Defaults to 10. */
+    maxConcurrentRequests?: number;
+    /* The number of row groups to fetch concurrently when streaming the file, unless `concurrency` is passed to the stream. Defaults to 1. */
+    concurrency?: number;
+    /* The maximum number of bytes of file data to keep in memory, so that later reads of the same byte ranges do not fetch them again. The least recently used ranges are evicted first. Disabled by default. */
+    cacheSize?: number;
+    /* Store the byte ranges fetched for the file with this adapter, e.g. backed by the Cache API or IndexedDB, and read them from it when the file is opened again. Keys combine the URL, the `ETag` of the file and the byte range. Files served without an `ETag`, e.g. because CORS does not expose it, are not cached. Opening the file makes an extra request for its `ETag`. */
+    cacheAdapter?: RangeCacheAdapter;
+    /* The metadata of the file, serialized with `ParquetMetaData.toBytes`, to open the file without fetching its footer. It must be the metadata of the same version of the file. With a `cacheAdapter`, `fromUrl` still requests the first byte of the file to find the ETag that cached ranges are stored under. */
+    metadata?: Uint8Array;
+    /* The number of bytes at the end of the file to fetch with the first request. If the footer and page index fit, the file is opened with a single request instead of 2 or 3. */
+    footerPrefetchBytes?: number;
+};
+
+export type FileRequest = {
+    /* The URL the file was opened with. */
+    url: string;
+    /* The value of the `Range` header of the request, e.g. `bytes=0-1023`. */
+    range: string;
+};
+
+export type RequestOverrides = {
+    /* The URL to request instead of the URL the file was opened with. */
+    url?: string;
+    headers?: Record<string, string>;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Options for opening a remote Parquet file
+    #[wasm_bindgen(typescript_type = "ParquetFileOptions")]
+    pub type ParquetFileOptions;
+}
+
+/// The credentials mode of a `fetch` request.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Credentials {
+    Omit,
+    SameOrigin,
+    Include,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsParquetFileOptions {
+    /// Cancel opening the file when this signal is aborted.
+    ///
+    /// This is parsed separately in `TryFrom<ParquetFileOptions>`, as it is a JS object.
+    #[serde(skip)]
+    pub signal: Option<Signal>,
+
+    /// Headers to send with every request for the file.
+    pub headers: Option<HashMap<String, String>>,
+
+    /// The credentials mode of every request for the file.
+    pub credentials: Option<Credentials>,
+
+    /// Called before every request for the file.
+    ///
+    /// This is parsed separately in `TryFrom<ParquetFileOptions>`, as it is a JS function.
+    #[serde(skip)]
+    pub prepare_request: Option<PrepareRequest>,
+
+    /// How to retry requests that fail with transient errors.
+    pub retry: Option<RetryOptions>,
+
+    /// Byte ranges separated by a gap of at most this many bytes are fetched with a single
+    /// request.
+    pub coalesce_size: Option<u64>,
+
+    /// The maximum number of bytes fetched by a single request.
+    pub max_request_size: Option<u64>,
+
+    /// The maximum number of concurrent requests made to fetch the ranges of a single read.
+    pub max_concurrent_requests: Option<usize>,
+
+    /// The default number of row groups to fetch concurrently.
+    pub concurrency: Option<usize>,
+
+    /// The maximum number of bytes of file data to cache.
+    pub cache_size: Option<u64>,
+
+    /// The number of bytes at the end of the file to fetch with the first request.
+    pub footer_prefetch_bytes: Option<usize>,
+
+    /// Store the fetched byte ranges of the file with this adapter.
+    ///
+    /// This is parsed separately in `TryFrom<ParquetFileOptions>`, as it is a JS object.
+    #[serde(skip)]
+    pub cache_adapter: Option<CacheAdapter>,
+
+    /// The serialized metadata of the file, to open it without fetching its footer.
+    ///
+    /// This is parsed separately in `TryFrom<ParquetFileOptions>`, as it is a `Uint8Array`.
+    #[serde(skip)]
+    pub metadata: Option<Bytes>,
+}
+
+impl JsParquetFileOptions {
+    /// How the reader fetches byte ranges, coalescing ranges with gaps of at most
+    /// `default_coalesce_size` bytes unless set otherwise.
+    pub(crate) fn io_options(&self, default_coalesce_size: u64) -> IoOptions {
+        IoOptions {
+            coalesce_size: self.coalesce_size.unwrap_or(default_coalesce_size),
+            max_request_size: self.max_request_size,
+            max_concurrent_requests: self
+                .max_concurrent_requests
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS),
+        }
+    }
+
+    /// The options of the requests for the file, which apply for as long as the file is used.
+    pub(crate) fn request_options(&self) -> Arc<RequestOptions> {
+        Arc::new(RequestOptions {
+            headers: self.headers.clone().unwrap_or_default(),
+            credentials: self.credentials,
+            prepare_request: self.prepare_request.clone(),
+            retry: self.retry.unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<ParquetFileOptions> for JsParquetFileOptions {
+    type Error = serde_wasm_bindgen::Error;
+
+    fn try_from(value: ParquetFileOptions) -> std::result::Result<Self, Self::Error> {
+        let signal = Signal::from_options(&value.obj)?;
+        let prepare_request = PrepareRequest::from_options(&value.obj)?;
+        let cache_adapter = CacheAdapter::from_options(&value.obj)?;
+        let metadata = metadata_from_options(&value.obj)?;
+        let mut options: Self = serde_wasm_bindgen::from_value(value.obj)?;
+        options.signal = signal;
+        options.prepare_request = prepare_request;
+        options.cache_adapter = cache_adapter;
+        options.metadata = metadata;
+        Ok(options)
+    }
+}
+
+/// Parse the `metadata` key of an options object.
+fn metadata_from_options(
+    options: &JsValue,
+) -> std::result::Result<Option<Bytes>, serde_wasm_bindgen::Error> {
+    let value = js_sys::Reflect::get(options, &"metadata".into()).unwrap_or(JsValue::UNDEFINED);
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    value
+        .dyn_into::<js_sys::Uint8Array>()
+        .map(|array| Some(array.to_vec().into()))
+        .map_err(|_| serde_wasm_bindgen::Error::new("metadata must be a Uint8Array"))
+}
+
+/// The request passed to a `prepareRequest` callback.
+#[derive(Serialize)]
+struct FileRequest<'a> {
+    url: &'a str,
+    range: &'a str,
+}
+
+/// The changes to a request returned by a `prepareRequest` callback.
+#[derive(Debug, Default, Deserialize)]
+struct RequestOverrides {
+    url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+}
+
+/// A `prepareRequest` callback passed from JS.
+#[derive(Clone, Debug)]
+pub struct PrepareRequest(js_sys::Function);
+
+/// Safety: This is not in fact thread-safe. Do not attempt to use this in work-stealing
+/// async runtimes / multi-threaded environments
+///
+/// The callback is held by `HTTPFileReader`, which must implement `AsyncFileReader` and so be
+/// `Send`. It is only ever called from local tasks.
+unsafe impl Send for PrepareRequest {}
+unsafe impl Sync for PrepareRequest {}
+
+impl PrepareRequest {
+    fn from_options(
+        options: &JsValue,
+    ) -> std::result::Result<Option<Self>, serde_wasm_bindgen::Error> {
+        let value =
+            js_sys::Reflect::get(options, &"prepareRequest".into()).unwrap_or(JsValue::UNDEFINED);
+        if value.is_undefined() || value.is_null() {
+            return Ok(None);
+        }
+        value
+            .dyn_into::<js_sys::Function>()
+            .map(|callback| Some(Self(callback)))
+            .map_err(|_| serde_wasm_bindgen::Error::new("prepareRequest must be a function"))
+    }
+
+    /// Call the callback, awaiting the result if it returns a promise.
+    async fn call(&self, url: &str, range: &str) -> Result<RequestOverrides> {
+        let callback_error = |err: JsValue| ParquetWasmError::CallbackError(js_error_message(&err));
+        let request = serde_wasm_bindgen::to_value(&FileRequest { url, range })
+            .map_err(|err| ParquetWasmError::CallbackError(err.to_string()))?;
+        let mut value = self
+            .0
+            .call1(&JsValue::NULL, &request)
+            .map_err(callback_error)?;
+        if let Some(promise) = value.dyn_ref::<js_sys::Promise>() {
+            value = JsFuture::from(promise.clone())
+                .await
+                .map_err(callback_error)?;
+        }
+        if value.is_undefined() || value.is_null() {
+            return Ok(Default::default());
+        }
+        serde_wasm_bindgen::from_value(value)
+            .map_err(|err| ParquetWasmError::CallbackError(err.to_string()))
+    }
+}
+
+/// How to make the requests for a remote file.
+#[derive(Debug, Default)]
+pub struct RequestOptions {
+    headers: HashMap<String, String>,
+    credentials: Option<Credentials>,
+    prepare_request: Option<PrepareRequest>,
+    pub(crate) retry: RetryOptions,
+}
+
+impl RequestOptions {
+    /// Create a request for the `range` of the file at `url`.
+    ///
+    /// This must be called from a local task, as it may call the `prepareRequest` callback.
+    pub(crate) async fn range_request(
+        &self,
+        client: &Client,
+        url: &str,
+        range: &str,
+    ) -> Result<RequestBuilder> {
+        // Header names are case-insensitive, so that e.g. a refreshed `authorization` header
+        // replaces a static `Authorization` header
+        let lowercase_names = |headers: HashMap<String, String>| {
+            headers
+                .into_iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value))
+        };
+        let mut url = url.to_string();
+        let mut headers = lowercase_names(self.headers.clone()).collect::<HashMap<_, _>>();
+        if let Some(prepare_request) = &self.prepare_request {
+            let overrides = prepare_request.call(&url, range).await?;
+            if let Some(new_url) = overrides.url {
+                url = new_url;
+            }
+            headers.extend(lowercase_names(overrides.headers.unwrap_or_default()));
+        }
+
+        let mut request = client.get(url);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        request = request.header("Range", range);
+        Ok(match self.credentials {
+            Some(Credentials::Omit) => request.fetch_credentials_omit(),
+            Some(Credentials::SameOrigin) => request.fetch_credentials_same_origin(),
+            Some(Credentials::Include) => request.fetch_credentials_include(),
+            None => request,
+        })
+    }
+}
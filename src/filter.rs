@@ -0,0 +1,369 @@
+//! Row filter expressions that are evaluated while decoding Parquet data.
+//!
+//! A [`FilterExpression`] is deserialized from a plain JS object and compiled into a parquet
+//! [`RowFilter`], so that only the columns referenced by the filter are decoded for rows that
+//! are later discarded.
+
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, BooleanArray, Datum, Float64Array, Int64Array, RecordBatch, Scalar,
+    StringArray,
+};
+use arrow::compute::kernels::{boolean, cmp, comparison};
+use arrow::compute::{CastOptions, cast, cast_with_options};
+use arrow::error::ArrowError;
+use arrow_schema::DataType;
+use parquet::arrow::arrow_reader::{ArrowPredicate, ArrowPredicateFn, RowFilter};
+use parquet::schema::types::SchemaDescriptor;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::error::Result;
+use crate::read_options::generate_projection_mask;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_FilterExpression: &'static str = r#"
+export type FilterValue = boolean | number | bigint | string;
+
+export type FilterExpression =
+    | { op: "==" | "!=" | "<" | "<=" | ">" | ">="; column: string; value: FilterValue }
+    | { op: "in"; column: string; values: FilterValue[] }
+    | { op: "isNull" | "isNotNull"; column: string }
+    | { op: "startsWith"; column: string; value: string }
+    | { op: "and" | "or"; filters: FilterExpression[] }
+    | { op: "not"; filter: FilterExpression };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A literal filter value
+    #[wasm_bindgen(typescript_type = "FilterValue")]
+    pub type JsFilterValue;
+}
+
+/// A literal value to compare a column against.
+///
+/// The value is cast to the data type of the column it is compared with, so e.g. a string can be
+/// used to compare against a timestamp column. A value that cannot be cast is an error, and
+/// numbers that do not fit an integer column are compared in a wider type, so that e.g. `x < 1.5`
+/// is not truncated to `x < 1`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterValue {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl FilterValue {
+    fn to_array(&self) -> ArrayRef {
+        match self {
+            Self::Boolean(value) => Arc::new(BooleanArray::from(vec![*value])),
+            Self::Integer(value) => Arc::new(Int64Array::from(vec![*value])),
+            Self::Float(value) => Arc::new(Float64Array::from(vec![*value])),
+            Self::String(value) => Arc::new(StringArray::from(vec![value.as_str()])),
+        }
+    }
+
+    /// The data type to cast a column of `data_type` to before comparing it with this value, if
+    /// the value does not fit the type of the column.
+    ///
+    /// Floats are compared with integer columns as `Float64`, and integers outside the range of an
+    /// integer column as `Int64`, so that the value is neither truncated nor overflowed.
+    fn widened_type(&self, data_type: &DataType) -> Option<DataType> {
+        let value_type = match data_type {
+            DataType::Dictionary(_, value_type) => value_type.as_ref(),
+            other => other,
+        };
+        if !value_type.is_integer() {
+            return None;
+        }
+        match self {
+            Self::Float(_) => Some(DataType::Float64),
+            Self::Integer(_) if cast_strict(&self.to_array(), value_type).is_err() => {
+                match value_type {
+                    DataType::UInt64 => Some(DataType::Float64),
+                    _ => Some(DataType::Int64),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Cast this value to a scalar to compare a column of the given data type with.
+    ///
+    /// Dictionary-encoded columns are compared against their value type.
+    pub(crate) fn to_scalar(
+        &self,
+        data_type: &DataType,
+    ) -> std::result::Result<Scalar<ArrayRef>, ArrowError> {
+        let array = match (self.widened_type(data_type), data_type) {
+            (Some(widened_type), _) => cast_strict(&self.to_array(), &widened_type)?,
+            (None, DataType::Dictionary(_, value_type)) => {
+                cast_strict(&self.to_array(), value_type)?
+            }
+            (None, other) => cast_strict(&self.to_array(), other)?,
+        };
+        Ok(Scalar::new(array))
+    }
+
+    /// Cast `array` to the data type it is compared with this value in, i.e. that of the scalar
+    /// returned by [`Self::to_scalar`].
+    pub(crate) fn comparable(&self, array: &ArrayRef) -> std::result::Result<ArrayRef, ArrowError> {
+        match self.widened_type(array.data_type()) {
+            Some(widened_type) => cast(array, &widened_type),
+            None => Ok(array.clone()),
+        }
+    }
+}
+
+/// Cast `array`, failing instead of silently producing nulls for values that cannot be cast.
+fn cast_strict(
+    array: &ArrayRef,
+    data_type: &DataType,
+) -> std::result::Result<ArrayRef, ArrowError> {
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    cast_with_options(array, data_type, &options)
+}
+
+impl TryFrom<JsFilterValue> for FilterValue {
+    type Error = serde_wasm_bindgen::Error;
+
+    fn try_from(value: JsFilterValue) -> std::result::Result<Self, Self::Error> {
+        serde_wasm_bindgen::from_value(value.obj)
+    }
+}
+
+/// A predicate over the rows of a Parquet file.
+///
+/// Comparisons follow SQL semantics: comparing a null value yields null, and rows where the
+/// filter evaluates to null are not selected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum FilterExpression {
+    #[serde(rename = "==")]
+    Eq { column: String, value: FilterValue },
+    #[serde(rename = "!=")]
+    NotEq { column: String, value: FilterValue },
+    #[serde(rename = "<")]
+    Lt { column: String, value: FilterValue },
+    #[serde(rename = "<=")]
+    LtEq { column: String, value: FilterValue },
+    #[serde(rename = ">")]
+    Gt { column: String, value: FilterValue },
+    #[serde(rename = ">=")]
+    GtEq { column: String, value: FilterValue },
+    #[serde(rename = "in")]
+    In {
+        column: String,
+        values: Vec<FilterValue>,
+    },
+    #[serde(rename = "isNull")]
+    IsNull { column: String },
+    #[serde(rename = "isNotNull")]
+    IsNotNull { column: String },
+    #[serde(rename = "startsWith")]
+    StartsWith { column: String, value: String },
+    #[serde(rename = "and")]
+    And { filters: Vec<FilterExpression> },
+    #[serde(rename = "or")]
+    Or { filters: Vec<FilterExpression> },
+    #[serde(rename = "not")]
+    Not { filter: Box<FilterExpression> },
+    /// A constant result for every row, where `None` is null.
+    ///
+    /// This is not part of the public filter syntax; it replaces conditions on columns that are
+    /// missing from a file.
+    #[serde(skip)]
+    Constant(Option<bool>),
+}
+
+type CompareFn = fn(&dyn Datum, &dyn Datum) -> std::result::Result<BooleanArray, ArrowError>;
+
+impl FilterExpression {
+    /// The names of all columns referenced by this expression.
+    pub fn columns(&self) -> Vec<&str> {
+        match self {
+            Self::Eq { column, .. }
+            | Self::NotEq { column, .. }
+            | Self::Lt { column, .. }
+            | Self::LtEq { column, .. }
+            | Self::Gt { column, .. }
+            | Self::GtEq { column, .. }
+            | Self::In { column, .. }
+            | Self::IsNull { column }
+            | Self::IsNotNull { column }
+            | Self::StartsWith { column, .. } => vec![column.as_str()],
+            Self::And { filters } | Self::Or { filters } => {
+                let mut columns: Vec<&str> = filters.iter().flat_map(|f| f.columns()).collect();
+                columns.sort_unstable();
+                columns.dedup();
+                columns
+            }
+            Self::Not { filter } => filter.columns(),
+            Self::Constant(_) => vec![],
+        }
+    }
+
+    /// Replace the conditions on columns for which `has_column` is false with their result on a
+    /// column of nulls.
+    pub fn with_missing_columns(&self, has_column: &dyn Fn(&str) -> bool) -> FilterExpression {
+        self.map_leaves(&|leaf| match leaf {
+            Self::IsNull { column } if !has_column(column) => Some(Self::Constant(Some(true))),
+            Self::IsNotNull { column } if !has_column(column) => Some(Self::Constant(Some(false))),
+            // Any other comparison with null is null
+            other => match other.columns().first() {
+                Some(column) if !has_column(column) => Some(Self::Constant(None)),
+                _ => None,
+            },
+        })
+    }
+
+    /// Replace the conditions that are not combinations of other conditions (i.e. all but `and`,
+    /// `or` and `not`).
+    ///
+    /// `replace` returns the new expression for a condition, or `None` to keep it.
+    pub fn map_leaves(
+        &self,
+        replace: &dyn Fn(&FilterExpression) -> Option<FilterExpression>,
+    ) -> FilterExpression {
+        match self {
+            Self::And { filters } => Self::And {
+                filters: filters.iter().map(|f| f.map_leaves(replace)).collect(),
+            },
+            Self::Or { filters } => Self::Or {
+                filters: filters.iter().map(|f| f.map_leaves(replace)).collect(),
+            },
+            Self::Not { filter } => Self::Not {
+                filter: Box::new(filter.map_leaves(replace)),
+            },
+            leaf => replace(leaf).unwrap_or_else(|| leaf.clone()),
+        }
+    }
+
+    /// The `==` and `in` conditions that every matching row must satisfy, as pairs of column and
+    /// candidate values.
+    ///
+    /// These are the conditions that can be checked against a bloom filter.
+    pub fn equality_conjuncts(&self) -> Vec<(&str, Vec<FilterValue>)> {
+        match self {
+            Self::Eq { column, value } => vec![(column.as_str(), vec![value.clone()])],
+            Self::In { column, values } => vec![(column.as_str(), values.clone())],
+            Self::And { filters } => filters
+                .iter()
+                .flat_map(|f| f.equality_conjuncts())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Evaluate this expression against a batch containing (at least) the referenced columns.
+    pub fn evaluate(&self, batch: &RecordBatch) -> std::result::Result<BooleanArray, ArrowError> {
+        match self {
+            Self::Eq { column, value } => compare(batch, column, value, cmp::eq),
+            Self::NotEq { column, value } => compare(batch, column, value, cmp::neq),
+            Self::Lt { column, value } => compare(batch, column, value, cmp::lt),
+            Self::LtEq { column, value } => compare(batch, column, value, cmp::lt_eq),
+            Self::Gt { column, value } => compare(batch, column, value, cmp::gt),
+            Self::GtEq { column, value } => compare(batch, column, value, cmp::gt_eq),
+            Self::In { column, values } => {
+                let array = column_by_path(batch, column)?;
+                let initial = BooleanArray::from(vec![false; batch.num_rows()]);
+                values.iter().try_fold(initial, |acc, value| {
+                    let scalar = value.to_scalar(array.data_type())?;
+                    let matches = cmp::eq(&value.comparable(&array)?, &scalar)?;
+                    boolean::or_kleene(&acc, &matches)
+                })
+            }
+            Self::IsNull { column } => boolean::is_null(column_by_path(batch, column)?.as_ref()),
+            Self::IsNotNull { column } => {
+                boolean::is_not_null(column_by_path(batch, column)?.as_ref())
+            }
+            Self::StartsWith { column, value } => compare(
+                batch,
+                column,
+                &FilterValue::String(value.clone()),
+                comparison::starts_with,
+            ),
+            Self::And { filters } => {
+                let initial = BooleanArray::from(vec![true; batch.num_rows()]);
+                filters.iter().try_fold(initial, |acc, filter| {
+                    boolean::and_kleene(&acc, &filter.evaluate(batch)?)
+                })
+            }
+            Self::Or { filters } => {
+                let initial = BooleanArray::from(vec![false; batch.num_rows()]);
+                filters.iter().try_fold(initial, |acc, filter| {
+                    boolean::or_kleene(&acc, &filter.evaluate(batch)?)
+                })
+            }
+            Self::Not { filter } => boolean::not(&filter.evaluate(batch)?),
+            Self::Constant(value) => Ok(BooleanArray::from(vec![*value; batch.num_rows()])),
+        }
+    }
+
+    /// The children of a top-level `and`, or else this expression itself.
+    pub fn conjuncts(&self) -> Vec<&FilterExpression> {
+        match self {
+            Self::And { filters } => filters.iter().collect(),
+            other => vec![other],
+        }
+    }
+
+    /// Compile this expression into a parquet [`RowFilter`].
+    ///
+    /// A top-level `and` is split into one predicate per child, so that each predicate is only
+    /// evaluated on the rows that passed the previous ones.
+    pub fn to_row_filter(&self, parquet_schema: &SchemaDescriptor) -> Result<RowFilter> {
+        let predicates = self
+            .conjuncts()
+            .into_iter()
+            .map(|expr| {
+                let projection = generate_projection_mask(&expr.columns(), parquet_schema)?;
+                let expr = expr.clone();
+                let predicate =
+                    ArrowPredicateFn::new(projection, move |batch| expr.evaluate(&batch));
+                Ok(Box::new(predicate) as Box<dyn ArrowPredicate>)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RowFilter::new(predicates))
+    }
+}
+
+fn compare(
+    batch: &RecordBatch,
+    column: &str,
+    value: &FilterValue,
+    op: CompareFn,
+) -> std::result::Result<BooleanArray, ArrowError> {
+    let array = column_by_path(batch, column)?;
+    let scalar = value.to_scalar(array.data_type())?;
+    op(&value.comparable(&array)?, &scalar)
+}
+
+/// Find a column by name, descending into struct columns for dotted paths like `a.b`.
+fn column_by_path(batch: &RecordBatch, path: &str) -> std::result::Result<ArrayRef, ArrowError> {
+    if let Some(column) = batch.column_by_name(path) {
+        return Ok(column.clone());
+    }
+
+    let mut parts = path.split('.');
+    let mut array = parts
+        .next()
+        .and_then(|name| batch.column_by_name(name))
+        .cloned();
+    for part in parts {
+        array = array.and_then(|array| {
+            array
+                .as_struct_opt()
+                .and_then(|struct_array| struct_array.column_by_name(part))
+                .cloned()
+        });
+    }
+    array.ok_or_else(|| ArrowError::SchemaError(format!("Column {path} not found in table")))
+}
@@ -1,16 +1,50 @@
 extern crate web_sys;
 
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod abort;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod bloom_filter;
+#[cfg(feature = "reader")]
+pub mod buffer;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod byte_source;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod cache;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod coalesce;
 pub mod common;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod dataset;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod partition;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod persistent_cache;
 pub mod utils;
 
 pub mod error;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod file_options;
+#[cfg(feature = "reader")]
+pub mod filter;
 pub mod metadata;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod progress;
+#[cfg(feature = "reader")]
+pub mod pruning;
 #[cfg(feature = "reader")]
 pub mod read_options;
 #[cfg(feature = "reader")]
 pub mod reader;
 #[cfg(all(feature = "reader", feature = "async"))]
 pub mod reader_async;
+#[cfg(all(feature = "reader", feature = "async"))]
+pub mod retry;
+#[cfg(feature = "reader")]
+pub mod row_numbers;
+#[cfg(feature = "reader")]
+pub mod target_schema;
+#[cfg(feature = "reader")]
+pub mod type_coercion;
 pub mod wasm;
 #[cfg(feature = "writer")]
 pub mod writer;
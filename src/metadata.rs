@@ -1,6 +1,9 @@
+use bytes::Bytes;
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaDataReader, ParquetMetaDataWriter};
 use wasm_bindgen::prelude::*;
 
 use crate::common::properties::{Compression, Encoding};
+use crate::error::{Result, WasmResult};
 
 /// Global Parquet metadata.
 #[derive(Debug, Clone)]
@@ -38,12 +41,31 @@ impl ParquetMetaData {
             .collect()
     }
 
+    /// Serialize the metadata as a Parquet footer, including the page index if it was loaded.
+    ///
+    /// Pass the bytes as the `metadata` option of {@linkcode ParquetFile.fromUrl} or
+    /// {@linkcode ParquetFile.fromFile} to open the same file without fetching its footer again,
+    /// e.g. in a Web Worker.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> WasmResult<Vec<u8>> {
+        let mut buffer = vec![];
+        ParquetMetaDataWriter::new(&mut buffer, &self.0).finish()?;
+        Ok(buffer)
+    }
+
     // /// Returns the column index for this file if loaded
     // pub fn column_index(&self) -> Option<ParquetColumnIndex> {
     //     self.0.column_index()
     // }
 }
 
+/// Parse metadata serialized by [`ParquetMetaData::to_bytes`].
+pub fn parse_metadata(bytes: &Bytes) -> Result<parquet::file::metadata::ParquetMetaData> {
+    Ok(ParquetMetaDataReader::new()
+        .with_page_index_policy(PageIndexPolicy::Optional)
+        .parse_and_finish(bytes)?)
+}
+
 impl From<parquet::file::metadata::ParquetMetaData> for ParquetMetaData {
     fn from(value: parquet::file::metadata::ParquetMetaData) -> Self {
         Self(value)
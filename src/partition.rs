@@ -0,0 +1,285 @@
+//! Hive-style partitioning, where the values of partition columns are encoded in the directory
+//! names of each file's path, e.g. `year=2024/month=1/part-0.parquet`.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, RecordBatch, RecordBatchOptions, StringArray, UInt32Array};
+use arrow::compute::{CastOptions, cast_with_options, take, take_record_batch};
+use arrow::error::ArrowError;
+use arrow_schema::{DataType, Field, Fields, Schema};
+
+use crate::filter::FilterExpression;
+
+/// The directory name that Hive and Spark use for null partition values
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// The partition values of the files of a dataset, as a batch with one row per file.
+#[derive(Clone, Debug)]
+pub struct Partitions {
+    values: RecordBatch,
+}
+
+impl Partitions {
+    /// Partitions without any partition columns.
+    pub fn empty(num_files: usize) -> Self {
+        let options = RecordBatchOptions::new().with_row_count(Some(num_files));
+        let values =
+            RecordBatch::try_new_with_options(Arc::new(Schema::empty()), vec![], &options).unwrap();
+        Self { values }
+    }
+
+    /// Parse the partition values of each file from its path or URL.
+    ///
+    /// Partition columns are ordered by their first appearance. Their type is inferred from all
+    /// values: integers, then dates (`YYYY-MM-DD`), then strings. Integers with a leading `0` or
+    /// `+`, such as zip codes, are kept as strings. A file without a value for a partition column
+    /// has a null value.
+    pub fn from_paths(paths: &[String]) -> Result<Self, ArrowError> {
+        let parsed = paths
+            .iter()
+            .map(|path| parse_path(path))
+            .collect::<Vec<_>>();
+
+        let mut keys: Vec<&str> = vec![];
+        for (key, _) in parsed.iter().flatten() {
+            if !keys.contains(&key.as_str()) {
+                keys.push(key);
+            }
+        }
+
+        let (fields, columns): (Vec<_>, Vec<_>) = keys
+            .into_iter()
+            .map(|key| {
+                let values: StringArray = parsed
+                    .iter()
+                    .map(|file_values| {
+                        file_values
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .and_then(|(_, value)| value.as_deref())
+                    })
+                    .collect();
+                let column = infer_column(values);
+                (Field::new(key, column.data_type().clone(), true), column)
+            })
+            .unzip();
+
+        let options = RecordBatchOptions::new().with_row_count(Some(paths.len()));
+        let values =
+            RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), columns, &options)?;
+        Ok(Self { values })
+    }
+
+    /// The partition columns.
+    pub fn fields(&self) -> &Fields {
+        self.values.schema_ref().fields()
+    }
+
+    pub fn is_partition_column(&self, column: &str) -> bool {
+        self.fields().find(column).is_some()
+    }
+
+    /// The partitions without the partition columns that are also in `fields`.
+    pub fn without_columns(&self, fields: &Fields) -> Result<Self, ArrowError> {
+        let indices = self
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| fields.find(field.name()).is_none())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        Ok(Self {
+            values: self.values.project(&indices)?,
+        })
+    }
+
+    /// The partitions of the files at `indices`.
+    pub fn select(&self, indices: &[usize]) -> Result<Self, ArrowError> {
+        let indices = UInt32Array::from_iter_values(indices.iter().map(|i| *i as u32));
+        Ok(Self {
+            values: take_record_batch(&self.values, &indices)?,
+        })
+    }
+
+    /// The indices of the files whose partition values may match `filter`.
+    ///
+    /// Conditions on columns that are not partition columns are unknown, and never exclude a
+    /// file on their own.
+    pub fn matching_files(&self, filter: &FilterExpression) -> Result<Vec<usize>, ArrowError> {
+        let filter = filter.map_leaves(&|leaf| match leaf.columns().first() {
+            Some(column) if !self.is_partition_column(column) => {
+                Some(FilterExpression::Constant(None))
+            }
+            _ => None,
+        });
+        let matches = filter.evaluate(&self.values)?;
+        Ok((0..matches.len())
+            .filter(|i| matches.is_null(*i) || matches.value(*i))
+            .collect())
+    }
+
+    /// Replace the conditions on partition columns with their result for a single file.
+    pub fn file_filter(
+        &self,
+        file_index: usize,
+        filter: &FilterExpression,
+    ) -> Result<FilterExpression, ArrowError> {
+        let file_values = self.values.slice(file_index, 1);
+        let error = RefCell::new(None);
+        let filter = filter.map_leaves(&|leaf| match leaf.columns().first() {
+            Some(column) if self.is_partition_column(column) => match leaf.evaluate(&file_values) {
+                Ok(result) => Some(FilterExpression::Constant(
+                    result.is_valid(0).then(|| result.value(0)),
+                )),
+                Err(err) => {
+                    error.borrow_mut().get_or_insert(err);
+                    None
+                }
+            },
+            _ => None,
+        });
+        match error.into_inner() {
+            Some(err) => Err(err),
+            None => Ok(filter),
+        }
+    }
+
+    /// Append the values of the partition columns in `fields` for a single file to one of its
+    /// batches.
+    pub fn append_columns(
+        &self,
+        file_index: usize,
+        batch: RecordBatch,
+        fields: &Fields,
+    ) -> Result<RecordBatch, ArrowError> {
+        if fields.is_empty() {
+            return Ok(batch);
+        }
+        let num_rows = batch.num_rows();
+        let mut columns = batch.columns().to_vec();
+        for field in fields {
+            columns.push(self.column(file_index, field.name(), num_rows)?);
+        }
+        let schema = Schema::new_with_metadata(
+            batch
+                .schema_ref()
+                .fields()
+                .iter()
+                .chain(fields)
+                .cloned()
+                .collect::<Fields>(),
+            batch.schema_ref().metadata().clone(),
+        );
+        let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+        RecordBatch::try_new_with_options(Arc::new(schema), columns, &options)
+    }
+
+    /// The value of a partition column for a single file, repeated `num_rows` times.
+    fn column(
+        &self,
+        file_index: usize,
+        column: &str,
+        num_rows: usize,
+    ) -> Result<ArrayRef, ArrowError> {
+        let values = self
+            .values
+            .column_by_name(column)
+            .ok_or_else(|| ArrowError::SchemaError(format!("Unknown partition column {column}")))?;
+        let indices = UInt32Array::from_value(file_index as u32, num_rows);
+        take(values, &indices, None)
+    }
+}
+
+/// The `key=value` directory names in a path or URL, in order.
+///
+/// Empty values and the Hive default partition are null.
+fn parse_path(path: &str) -> Vec<(String, Option<String>)> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/').collect::<Vec<_>>();
+    // Partition values are only encoded in directory names, not in the file name
+    segments.pop();
+    segments
+        .into_iter()
+        .filter_map(|segment| {
+            let (key, value) = segment.split_once('=')?;
+            if key.is_empty() {
+                return None;
+            }
+            let value = percent_decode(value);
+            let value = (!value.is_empty() && value != HIVE_DEFAULT_PARTITION).then_some(value);
+            Some((percent_decode(key), value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes, as used by Hive and Spark for special characters in directory names.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes.get(i..i + 3) {
+            Some([b'%', high, low]) if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            }
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| value.to_string())
+}
+
+/// Cast string partition values to the narrowest type that all values can be parsed as.
+fn infer_column(values: StringArray) -> ArrayRef {
+    let options = CastOptions {
+        safe: true,
+        ..Default::default()
+    };
+    let is_date = |value: &str| {
+        let bytes = value.as_bytes();
+        bytes.len() == 10
+            && bytes.iter().enumerate().all(|(i, byte)| match i {
+                4 | 7 => *byte == b'-',
+                _ => byte.is_ascii_digit(),
+            })
+    };
+    // Values such as `01234` or `+7` would not round-trip as integers, e.g. zip codes
+    let is_integer = |value: &str| {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        !digits.is_empty()
+            && digits.bytes().all(|byte| byte.is_ascii_digit())
+            && (digits == "0" || !digits.starts_with('0'))
+    };
+
+    if values.null_count() == values.len() {
+        return Arc::new(values);
+    }
+    for data_type in [DataType::Int32, DataType::Int64, DataType::Date32] {
+        // Only read strictly formatted dates as dates, so that e.g. `2024-1` stays a string
+        if data_type == DataType::Date32 && !values.iter().flatten().all(is_date) {
+            continue;
+        }
+        if data_type != DataType::Date32 && !values.iter().flatten().all(is_integer) {
+            continue;
+        }
+        if let Ok(column) = cast_with_options(&values, &data_type, &options) {
+            // Values that cannot be parsed are null after a safe cast
+            if column.null_count() == values.null_count() {
+                return column;
+            }
+        }
+    }
+    Arc::new(values)
+}
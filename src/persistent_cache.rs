@@ -0,0 +1,133 @@
+//! Store the byte ranges fetched for a remote file with a JS cache adapter, e.g. backed by the
+//! Cache API or IndexedDB, so that they are not fetched again when the file is reopened.
+//!
+//! Entries are keyed by the URL the file was opened with, its `ETag` and the byte range, so that
+//! entries of an older version of the file are never read. Files served without an `ETag` are
+//! not cached. Errors of the adapter are ignored, so that a failing cache falls back to the
+//! network instead of failing the read.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::channel::oneshot;
+use js_sys::{Function, Promise, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{JsFuture, spawn_local};
+
+use crate::utils::js_bytes;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_RangeCacheAdapter: &'static str = r#"
+export type RangeCacheAdapter = {
+    /* Return the bytes stored under `key`, or `undefined` if there are none. */
+    get(key: string): Promise<Uint8Array | ArrayBuffer | undefined>;
+    /* Store `bytes` under `key`. */
+    put(key: string, bytes: Uint8Array): Promise<void>;
+};
+"#;
+
+/// A cache adapter passed from JS.
+#[derive(Clone, Debug)]
+pub struct CacheAdapter {
+    adapter: JsValue,
+    get: Function,
+    put: Function,
+}
+
+/// Safety: This is not in fact thread-safe. Do not attempt to use this in work-stealing
+/// async runtimes / multi-threaded environments
+///
+/// The adapter is held by `HTTPFileReader`, which must implement `AsyncFileReader` and so be
+/// `Send`. It is only ever called from local tasks.
+unsafe impl Send for CacheAdapter {}
+unsafe impl Sync for CacheAdapter {}
+
+impl CacheAdapter {
+    /// Parse the `cacheAdapter` key of an options object.
+    pub(crate) fn from_options(
+        options: &JsValue,
+    ) -> std::result::Result<Option<Self>, serde_wasm_bindgen::Error> {
+        let adapter = Reflect::get(options, &"cacheAdapter".into()).unwrap_or(JsValue::UNDEFINED);
+        if adapter.is_undefined() || adapter.is_null() {
+            return Ok(None);
+        }
+        let method = |name: &str| {
+            Reflect::get(&adapter, &name.into())
+                .ok()
+                .and_then(|value| value.dyn_into::<Function>().ok())
+        };
+        match (method("get"), method("put")) {
+            (Some(get), Some(put)) => Ok(Some(Self { adapter, get, put })),
+            _ => Err(serde_wasm_bindgen::Error::new(
+                "cacheAdapter must have get and put methods",
+            )),
+        }
+    }
+
+    /// Call a method of the adapter, awaiting the result if it returns a promise.
+    async fn call(&self, method: &Function, args: &[JsValue]) -> Option<JsValue> {
+        let value = match args {
+            [key] => method.call1(&self.adapter, key),
+            [key, bytes] => method.call2(&self.adapter, key, bytes),
+            _ => return None,
+        }
+        .ok()?;
+        match value.dyn_into::<Promise>() {
+            Ok(promise) => JsFuture::from(promise).await.ok(),
+            Err(value) => Some(value),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Option<Bytes> {
+        let value = self.call(&self.get, &[key.into()]).await?;
+        js_bytes(&value).map(Bytes::from)
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8]) {
+        let array = Uint8Array::from(bytes);
+        self.call(&self.put, &[key.into(), array.into()]).await;
+    }
+}
+
+/// The persistent cache of the byte ranges of a remote file.
+#[derive(Debug)]
+pub struct PersistentCache {
+    adapter: CacheAdapter,
+    url: String,
+    etag: String,
+}
+
+impl PersistentCache {
+    /// Cache the ranges of the file at `url` with the given `ETag`.
+    pub fn new(adapter: CacheAdapter, url: String, etag: String) -> Arc<Self> {
+        Arc::new(Self { adapter, url, etag })
+    }
+
+    /// The key of a range, given as the value of a `Range` header.
+    fn key(&self, range: &str) -> String {
+        format!("{} {} {}", self.url, self.etag, range)
+    }
+
+    /// The cached bytes of a range, if they exist and have the `expected_len`.
+    pub(crate) async fn get(&self, range: &str, expected_len: Option<u64>) -> Option<Bytes> {
+        let (sender, receiver) = oneshot::channel();
+        let adapter = self.adapter.clone();
+        let key = self.key(range);
+        spawn_local(async move {
+            let _ = sender.send(adapter.get(&key).await);
+        });
+        let bytes = receiver.await.ok().flatten()?;
+        expected_len
+            .is_none_or(|len| bytes.len() as u64 == len)
+            .then_some(bytes)
+    }
+
+    /// Store the bytes of a range in the background.
+    pub(crate) fn put(&self, range: &str, bytes: Bytes) {
+        let adapter = self.adapter.clone();
+        let key = self.key(range);
+        spawn_local(async move {
+            adapter.put(&key, &bytes).await;
+        });
+    }
+}
@@ -0,0 +1,92 @@
+//! Report the progress of asynchronous reads to a JS callback.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ReadProgress: &'static str = r#"
+export type ReadProgress = {
+    /* The number of bytes of file data requested so far. */
+    bytesRequested: number;
+    /* The number of bytes of file data received so far. */
+    bytesReceived: number;
+    /* The number of row groups that have been read completely. */
+    rowGroupsCompleted: number;
+    /* The number of row groups to read, after pruning with the filter. */
+    rowGroupsTotal: number;
+    /* The number of rows decoded so far, before the offset and limit of a stream are applied. */
+    rowsDecoded: number;
+};
+"#;
+
+/// The progress of a read.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadProgress {
+    pub bytes_requested: u64,
+    pub bytes_received: u64,
+    pub row_groups_completed: usize,
+    pub row_groups_total: usize,
+    pub rows_decoded: usize,
+}
+
+/// A JS function to call with the progress of a read.
+#[derive(Clone, Debug)]
+struct Callback(js_sys::Function);
+
+/// Safety: This is not in fact thread-safe. Do not attempt to use this in work-stealing
+/// async runtimes / multi-threaded environments
+///
+/// The callback is held by readers that must implement `AsyncFileReader`, which requires `Send`.
+unsafe impl Send for Callback {}
+unsafe impl Sync for Callback {}
+
+/// The progress of a read, shared by the readers and streams that make up the read.
+#[derive(Clone, Debug)]
+pub struct Progress {
+    callback: Callback,
+    state: Arc<Mutex<ReadProgress>>,
+}
+
+impl Progress {
+    /// Parse the `onProgress` key of an options object.
+    pub(crate) fn from_options(
+        options: &JsValue,
+    ) -> std::result::Result<Option<Self>, serde_wasm_bindgen::Error> {
+        let value =
+            js_sys::Reflect::get(options, &"onProgress".into()).unwrap_or(JsValue::UNDEFINED);
+        if value.is_undefined() || value.is_null() {
+            return Ok(None);
+        }
+        let callback = value
+            .dyn_into::<js_sys::Function>()
+            .map_err(|_| serde_wasm_bindgen::Error::new("onProgress must be a function"))?;
+        Ok(Some(Self {
+            callback: Callback(callback),
+            state: Default::default(),
+        }))
+    }
+
+    /// Update the progress and report it to the callback.
+    ///
+    /// Errors thrown by the callback are ignored, so that they do not fail the read.
+    pub(crate) fn update(&self, update: impl FnOnce(&mut ReadProgress)) {
+        let progress = {
+            let mut state = self.state.lock().unwrap();
+            update(&mut state);
+            state.clone()
+        };
+        if let Ok(progress) = serde_wasm_bindgen::to_value(&progress) {
+            let _ = self.callback.0.call1(&JsValue::NULL, &progress);
+        }
+    }
+}
+
+/// Update the progress of a read, if it is being reported.
+pub(crate) fn report(progress: Option<&Progress>, update: impl FnOnce(&mut ReadProgress)) {
+    if let Some(progress) = progress {
+        progress.update(update);
+    }
+}
@@ -0,0 +1,329 @@
+//! Skip data that cannot contain rows matching a [`FilterExpression`], using the min/max and null
+//! count statistics stored in the Parquet metadata.
+//!
+//! Statistics are only ever used to exclude data: whenever they are missing or cannot be compared
+//! with a filter value, the data is assumed to match and the row filter decides.
+
+use arrow::array::{Array, ArrayRef, BooleanArray, Scalar, UInt64Array};
+use arrow::compute::kernels::{boolean, cmp, comparison};
+use arrow::error::ArrowError;
+use arrow_schema::Schema;
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::arrow::arrow_reader::{RowSelection, RowSelector};
+use parquet::file::metadata::{ParquetColumnIndex, ParquetMetaData, ParquetOffsetIndex};
+use parquet::schema::types::SchemaDescriptor;
+
+use crate::filter::{FilterExpression, FilterValue};
+
+/// Statistics of a single column for a number of containers, i.e. row groups or data pages.
+pub(crate) struct ColumnStatistics {
+    pub mins: ArrayRef,
+    pub maxes: ArrayRef,
+    pub null_counts: UInt64Array,
+    pub row_counts: Option<UInt64Array>,
+}
+
+/// Evaluate a single-column expression against the statistics of that column.
+///
+/// The result is `false` for containers that cannot contain matching rows, and `true` or null
+/// otherwise. Returns `None` if the expression cannot be evaluated from statistics.
+pub(crate) fn leaf_may_match(
+    expr: &FilterExpression,
+    stats: &ColumnStatistics,
+) -> Option<BooleanArray> {
+    try_leaf_may_match(expr, stats).ok().flatten()
+}
+
+fn try_leaf_may_match(
+    expr: &FilterExpression,
+    stats: &ColumnStatistics,
+) -> Result<Option<BooleanArray>, ArrowError> {
+    // The statistics are cast along with the value, if the value does not fit their type
+    let bounds = |value: &FilterValue| -> Result<_, ArrowError> {
+        let scalar = value.to_scalar(stats.mins.data_type())?;
+        Ok((
+            value.comparable(&stats.mins)?,
+            value.comparable(&stats.maxes)?,
+            scalar,
+        ))
+    };
+    let may_match = match expr {
+        FilterExpression::Eq { value, .. } => {
+            let (mins, maxes, value) = bounds(value)?;
+            eq_may_match(&mins, &maxes, &value)?
+        }
+        FilterExpression::NotEq { value, .. } => {
+            // Only exclude containers where every non-null value equals the filter value
+            let (mins, maxes, value) = bounds(value)?;
+            let all_equal =
+                boolean::and_kleene(&cmp::eq(&mins, &value)?, &cmp::eq(&maxes, &value)?)?;
+            boolean::not(&all_equal)?
+        }
+        FilterExpression::Lt { value, .. } => {
+            let (mins, _, value) = bounds(value)?;
+            cmp::lt(&mins, &value)?
+        }
+        FilterExpression::LtEq { value, .. } => {
+            let (mins, _, value) = bounds(value)?;
+            cmp::lt_eq(&mins, &value)?
+        }
+        FilterExpression::Gt { value, .. } => {
+            let (_, maxes, value) = bounds(value)?;
+            cmp::gt(&maxes, &value)?
+        }
+        FilterExpression::GtEq { value, .. } => {
+            let (_, maxes, value) = bounds(value)?;
+            cmp::gt_eq(&maxes, &value)?
+        }
+        FilterExpression::In { values, .. } => {
+            let initial = BooleanArray::from(vec![false; stats.mins.len()]);
+            values.iter().try_fold(initial, |acc, value| {
+                let (mins, maxes, value) = bounds(value)?;
+                boolean::or_kleene(&acc, &eq_may_match(&mins, &maxes, &value)?)
+            })?
+        }
+        FilterExpression::IsNull { .. } => {
+            cmp::gt(&stats.null_counts, &Scalar::new(UInt64Array::from(vec![0])))?
+        }
+        FilterExpression::IsNotNull { .. } => match &stats.row_counts {
+            Some(row_counts) => cmp::lt(&stats.null_counts, row_counts)?,
+            None => return Ok(None),
+        },
+        FilterExpression::StartsWith { value, .. } => {
+            // Any value with this prefix sorts at or after the prefix itself, and a minimum that
+            // sorts after the prefix without starting with it sorts after all such values.
+            let prefix = FilterValue::String(value.clone()).to_scalar(stats.mins.data_type())?;
+            let min_in_range = boolean::or_kleene(
+                &cmp::lt_eq(&stats.mins, &prefix)?,
+                &comparison::starts_with(&stats.mins, &prefix)?,
+            )?;
+            boolean::and_kleene(&cmp::gt_eq(&stats.maxes, &prefix)?, &min_in_range)?
+        }
+        FilterExpression::Constant(value) => BooleanArray::from(vec![*value; stats.mins.len()]),
+        FilterExpression::And { .. }
+        | FilterExpression::Or { .. }
+        | FilterExpression::Not { .. } => {
+            return Ok(None);
+        }
+    };
+    Ok(Some(may_match))
+}
+
+fn eq_may_match(
+    mins: &ArrayRef,
+    maxes: &ArrayRef,
+    value: &Scalar<ArrayRef>,
+) -> Result<BooleanArray, ArrowError> {
+    boolean::and_kleene(&cmp::lt_eq(mins, value)?, &cmp::gt_eq(maxes, value)?)
+}
+
+/// Return the subset of `row_groups` that may contain rows matching `filter`, based on the column
+/// chunk statistics in the file metadata.
+///
+/// No data needs to be fetched to decide this, so row groups that are pruned here are never
+/// requested.
+pub fn prune_row_groups(
+    filter: &FilterExpression,
+    arrow_schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    metadata: &ParquetMetaData,
+    row_groups: Vec<usize>,
+) -> Vec<usize> {
+    let may_match =
+        row_groups_may_match(filter, arrow_schema, parquet_schema, metadata, &row_groups);
+    row_groups
+        .into_iter()
+        .zip(may_match.iter())
+        .filter(|(_, may_match)| *may_match != Some(false))
+        .map(|(row_group, _)| row_group)
+        .collect()
+}
+
+fn row_groups_may_match(
+    expr: &FilterExpression,
+    arrow_schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+) -> BooleanArray {
+    let unknown = || BooleanArray::new_null(row_groups.len());
+    let children_may_match =
+        |filter| row_groups_may_match(filter, arrow_schema, parquet_schema, metadata, row_groups);
+    match expr {
+        FilterExpression::And { filters } => {
+            let initial = BooleanArray::from(vec![true; row_groups.len()]);
+            filters.iter().fold(initial, |acc, filter| {
+                boolean::and_kleene(&acc, &children_may_match(filter)).unwrap_or_else(|_| unknown())
+            })
+        }
+        FilterExpression::Or { filters } => {
+            let initial = BooleanArray::from(vec![false; row_groups.len()]);
+            filters.iter().fold(initial, |acc, filter| {
+                boolean::or_kleene(&acc, &children_may_match(filter)).unwrap_or_else(|_| unknown())
+            })
+        }
+        FilterExpression::Not { .. } => unknown(),
+        FilterExpression::Constant(value) => BooleanArray::from(vec![*value; row_groups.len()]),
+        _ => expr
+            .columns()
+            .first()
+            .and_then(|column| {
+                row_group_statistics(column, arrow_schema, parquet_schema, metadata, row_groups)
+            })
+            .and_then(|stats| leaf_may_match(expr, &stats))
+            .unwrap_or_else(unknown),
+    }
+}
+
+fn row_group_statistics(
+    column: &str,
+    arrow_schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+) -> Option<ColumnStatistics> {
+    let converter = StatisticsConverter::try_new(column, arrow_schema, parquet_schema)
+        .ok()?
+        .with_missing_null_counts_as_zero(false);
+    let row_group_metadatas = row_groups
+        .iter()
+        .map(|i| metadata.row_groups().get(*i))
+        .collect::<Option<Vec<_>>>()?;
+    let row_group_metadatas = || row_group_metadatas.iter().copied();
+    Some(ColumnStatistics {
+        mins: converter.row_group_mins(row_group_metadatas()).ok()?,
+        maxes: converter.row_group_maxes(row_group_metadatas()).ok()?,
+        null_counts: converter
+            .row_group_null_counts(row_group_metadatas())
+            .ok()?,
+        row_counts: converter.row_group_row_counts(row_group_metadatas()).ok()?,
+    })
+}
+
+/// Build a [`RowSelection`] over `row_groups` that skips the data pages that cannot contain rows
+/// matching `filter`, based on the page index.
+///
+/// When used with an offset index, the reader only fetches the byte ranges of the selected pages.
+/// Returns `None` if the file metadata does not contain a complete page index.
+///
+/// The offset and limit of a read are not part of this selection, as they count the rows that
+/// match the filter. The reader applies them to the selection after evaluating the row filter, so
+/// only the pages of the rows within the offset and limit are fetched for the other columns.
+pub fn page_selection(
+    filter: &FilterExpression,
+    arrow_schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+) -> Option<RowSelection> {
+    let column_index = metadata.column_index()?;
+    let offset_index = metadata.offset_index()?;
+
+    // A page index that is missing for some row groups would make the statistics converter panic
+    let num_columns = parquet_schema.num_columns();
+    let is_complete = row_groups.iter().all(|i| {
+        column_index.get(*i).is_some_and(|c| c.len() == num_columns)
+            && offset_index.get(*i).is_some_and(|c| c.len() == num_columns)
+    });
+    if !is_complete {
+        return None;
+    }
+
+    let page_index = PageIndex {
+        arrow_schema,
+        parquet_schema,
+        metadata,
+        column_index,
+        offset_index,
+        row_groups,
+    };
+    Some(page_index.selection(filter))
+}
+
+struct PageIndex<'a> {
+    arrow_schema: &'a Schema,
+    parquet_schema: &'a SchemaDescriptor,
+    metadata: &'a ParquetMetaData,
+    column_index: &'a ParquetColumnIndex,
+    offset_index: &'a ParquetOffsetIndex,
+    row_groups: &'a [usize],
+}
+
+impl PageIndex<'_> {
+    fn num_rows(&self) -> usize {
+        self.row_groups
+            .iter()
+            .map(|i| self.metadata.row_group(*i).num_rows() as usize)
+            .sum()
+    }
+
+    fn select_all(&self) -> RowSelection {
+        RowSelection::from(vec![RowSelector::select(self.num_rows())])
+    }
+
+    fn skip_all(&self) -> RowSelection {
+        RowSelection::from(vec![RowSelector::skip(self.num_rows())])
+    }
+
+    fn selection(&self, expr: &FilterExpression) -> RowSelection {
+        match expr {
+            FilterExpression::And { filters } => {
+                filters.iter().fold(self.select_all(), |acc, filter| {
+                    acc.intersection(&self.selection(filter))
+                })
+            }
+            FilterExpression::Or { filters } => {
+                filters.iter().fold(self.skip_all(), |acc, filter| {
+                    acc.union(&self.selection(filter))
+                })
+            }
+            FilterExpression::Not { .. } => self.select_all(),
+            FilterExpression::Constant(Some(true)) => self.select_all(),
+            FilterExpression::Constant(_) => self.skip_all(),
+            _ => self
+                .leaf_selection(expr)
+                .unwrap_or_else(|| self.select_all()),
+        }
+    }
+
+    fn leaf_selection(&self, expr: &FilterExpression) -> Option<RowSelection> {
+        let column = expr.columns().first().copied()?;
+        let converter =
+            StatisticsConverter::try_new(column, self.arrow_schema, self.parquet_schema)
+                .ok()?
+                .with_missing_null_counts_as_zero(false);
+        let stats = ColumnStatistics {
+            mins: converter
+                .data_page_mins(self.column_index, self.offset_index, self.row_groups)
+                .ok()?,
+            maxes: converter
+                .data_page_maxes(self.column_index, self.offset_index, self.row_groups)
+                .ok()?,
+            null_counts: converter
+                .data_page_null_counts(self.column_index, self.offset_index, self.row_groups)
+                .ok()?,
+            row_counts: converter
+                .data_page_row_counts(
+                    self.offset_index,
+                    self.metadata.row_groups(),
+                    self.row_groups,
+                )
+                .ok()?,
+        };
+        let may_match = leaf_may_match(expr, &stats)?;
+        let row_counts = stats.row_counts?;
+        let selectors =
+            row_counts
+                .values()
+                .iter()
+                .zip(may_match.iter())
+                .map(|(row_count, may_match)| {
+                    if may_match == Some(false) {
+                        RowSelector::skip(*row_count as usize)
+                    } else {
+                        RowSelector::select(*row_count as usize)
+                    }
+                });
+        Some(selectors.collect())
+    }
+}
@@ -1,10 +1,26 @@
+use std::sync::Arc;
+
+use arrow::error::ArrowError;
+use arrow_schema::{Field, SchemaBuilder, SchemaRef};
+use js_sys::Reflect;
 use parquet::arrow::ProjectionMask;
-use parquet::arrow::arrow_reader::ArrowReaderBuilder;
+use parquet::arrow::arrow_reader::{ArrowReaderBuilder, ArrowReaderMetadata, ArrowReaderOptions};
+use parquet::errors::ParquetError;
 use parquet::schema::types::SchemaDescriptor;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "async")]
+use crate::abort::Signal;
 use crate::error::{ParquetWasmError, Result};
+use crate::filter::FilterExpression;
+#[cfg(feature = "async")]
+use crate::progress::Progress;
+use crate::pruning::{page_selection, prune_row_groups};
+use crate::reader::cast_metadata_view_types;
+use crate::row_numbers::{ROW_NUMBER_TYPE, RowNumbers};
+use crate::target_schema;
+use crate::type_coercion::TypeCoercion;
 
 #[wasm_bindgen(typescript_custom_section)]
 const TS_ReaderOptions: &'static str = r#"
@@ -21,6 +37,20 @@ export type ReaderOptions = {
     columns?: string[];
     /* The number of concurrent requests to make in the async reader. */
     concurrency?: number;
+    /* Only read rows matching this filter. Columns not referenced by the filter are only decoded for matching rows. */
+    filter?: FilterExpression;
+    /* Convert the Arrow types read from the file into types that are easier to use from JS. */
+    typeCoercion?: TypeCoercion;
+    /* Read the columns of the file as the types of the fields with the same name in this schema. Either an Arrow IPC stream, e.g. from `Schema.intoIPCStream()`, or a {@linkcode SchemaDescription}. */
+    schema?: Uint8Array | SchemaDescription;
+    /* Infer the Arrow schema from the Parquet schema only, ignoring the Arrow schema stored in the file by the writer. */
+    skipArrowMetadata?: boolean;
+    /* Append a UInt64 column with this name holding the 0-based position of each row in the file. */
+    rowNumberColumn?: string;
+    /* Cancel the read when this signal is aborted. In-flight requests are aborted and the read rejects with an abort error. Only used by the async reader. */
+    signal?: AbortSignal;
+    /* Called with the {@linkcode ReadProgress} whenever file data is received, a batch is decoded or a row group is completed. Only used by the async reader. */
+    onProgress?: (progress: ReadProgress) => void;
 };
 "#;
 
@@ -51,13 +81,90 @@ pub struct JsReaderOptions {
 
     /// The number of concurrent requests to make in the async reader.
     pub concurrency: Option<usize>,
+
+    /// Only read rows matching this filter.
+    pub filter: Option<FilterExpression>,
+
+    /// Convert the Arrow types read from the file.
+    pub type_coercion: Option<TypeCoercion>,
+
+    /// Read the columns of the file as the types of the fields with the same name in this schema.
+    ///
+    /// This is parsed separately in `TryFrom<ReaderOptions>`, as it may be an IPC buffer.
+    #[serde(skip)]
+    pub schema: Option<SchemaRef>,
+
+    /// Ignore the Arrow schema stored in the file by the writer.
+    pub skip_arrow_metadata: Option<bool>,
+
+    /// Append a column with this name holding the position of each row in the file.
+    pub row_number_column: Option<String>,
+
+    /// Cancel the read when this signal is aborted.
+    ///
+    /// This is parsed separately in `TryFrom<ReaderOptions>`, as it is a JS object.
+    #[cfg(feature = "async")]
+    #[serde(skip)]
+    pub signal: Option<Signal>,
+
+    /// Report the progress of the read to this callback.
+    ///
+    /// This is parsed separately in `TryFrom<ReaderOptions>`, as it is a JS function.
+    #[cfg(feature = "async")]
+    #[serde(skip)]
+    pub on_progress: Option<Progress>,
 }
 
 impl JsReaderOptions {
+    /// Apply the options that change how the file is decoded to the reader metadata.
+    pub(crate) fn apply_to_metadata(
+        &self,
+        metadata: &ArrowReaderMetadata,
+    ) -> Result<ArrowReaderMetadata> {
+        let metadata = if self.skip_arrow_metadata.unwrap_or(false) {
+            let arrow_options = ArrowReaderOptions::default().with_skip_arrow_metadata(true);
+            ArrowReaderMetadata::try_new(metadata.metadata().clone(), arrow_options)?
+        } else {
+            metadata.clone()
+        };
+
+        // Cast any view types to non-view types, then apply any requested type conversions
+        let metadata = cast_metadata_view_types(&metadata)?;
+        let metadata = self
+            .type_coercion
+            .unwrap_or_default()
+            .apply_to_metadata(&metadata)?;
+        match &self.schema {
+            Some(schema) => target_schema::apply_to_metadata(&metadata, schema),
+            None => Ok(metadata),
+        }
+    }
+
+    /// The schema of the batches returned to the user, given the schema of the decoded batches.
+    pub(crate) fn output_schema(&self, schema: &SchemaRef) -> SchemaRef {
+        let schema = self.type_coercion.unwrap_or_default().output_schema(schema);
+        let schema = match &self.schema {
+            Some(target) => target_schema::output_schema(&schema, target),
+            None => schema,
+        };
+        match &self.row_number_column {
+            Some(name) => {
+                let mut builder = SchemaBuilder::from(schema.as_ref());
+                builder.push(Field::new(name, ROW_NUMBER_TYPE, false));
+                Arc::new(builder.finish())
+            }
+            None => schema,
+        }
+    }
+
+    /// Apply these options to a reader builder.
+    ///
+    /// If row numbers were requested, this also returns the [`RowNumbers`] to append to each
+    /// batch that the reader returns.
     pub fn apply_to_builder<T>(
         &self,
         mut builder: ArrowReaderBuilder<T>,
-    ) -> Result<ArrowReaderBuilder<T>> {
+    ) -> Result<(ArrowReaderBuilder<T>, Option<RowNumbers>)> {
         if let Some(batch_size) = self.batch_size {
             builder = builder.with_batch_size(batch_size);
         }
@@ -77,11 +184,82 @@ impl JsReaderOptions {
             builder = builder.with_projection(projection_mask);
         }
 
-        if let Some(row_groups) = &self.row_groups {
+        // Checked before pruning, as the statistics and row numbers are looked up by row group
+        let mut row_groups = self.row_groups_or_all(builder.metadata().num_row_groups())?;
+        let mut selection = None;
+        if let Some(filter) = &self.filter {
+            row_groups = prune_row_groups(
+                filter,
+                builder.schema(),
+                builder.parquet_schema(),
+                builder.metadata(),
+                row_groups,
+            );
+            selection = page_selection(
+                filter,
+                builder.schema(),
+                builder.parquet_schema(),
+                builder.metadata(),
+                &row_groups,
+            );
+            // The reader narrows this selection down to the offset and limit once it has evaluated
+            // the row filter
+            if let Some(selection) = &selection {
+                builder = builder.with_row_selection(selection.clone());
+            }
+        }
+        if self.row_groups.is_some() || self.filter.is_some() {
             builder = builder.with_row_groups(row_groups.clone());
         }
 
-        Ok(builder)
+        let row_numbers = match &self.row_number_column {
+            Some(name) => {
+                if builder.schema().field_with_name(name).is_ok() {
+                    return Err(ArrowError::SchemaError(format!(
+                        "Row number column {name} is also a column of the file"
+                    ))
+                    .into());
+                }
+                let (row_numbers, row_filter) = RowNumbers::try_new(
+                    name,
+                    builder.metadata(),
+                    builder.parquet_schema(),
+                    &row_groups,
+                    selection.as_ref(),
+                    self.filter.as_ref(),
+                    self.offset,
+                )?;
+                if let Some(row_filter) = row_filter {
+                    builder = builder.with_row_filter(row_filter);
+                }
+                Some(row_numbers)
+            }
+            None => {
+                if let Some(filter) = &self.filter {
+                    let row_filter = filter.to_row_filter(builder.parquet_schema())?;
+                    builder = builder.with_row_filter(row_filter);
+                }
+                None
+            }
+        };
+
+        Ok((builder, row_numbers))
+    }
+
+    /// The row groups selected by these options, or all row groups if none were provided.
+    ///
+    /// Errors if a selected row group is not in a file with `num_row_groups` row groups.
+    pub fn row_groups_or_all(&self, num_row_groups: usize) -> Result<Vec<usize>> {
+        let Some(row_groups) = &self.row_groups else {
+            return Ok((0..num_row_groups).collect());
+        };
+        if let Some(row_group) = row_groups.iter().find(|i| **i >= num_row_groups) {
+            return Err(ParquetError::General(format!(
+                "Row group {row_group} out of bounds for file with {num_row_groups} row groups"
+            ))
+            .into());
+        }
+        Ok(row_groups.clone())
     }
 }
 
@@ -89,11 +267,23 @@ impl TryFrom<ReaderOptions> for JsReaderOptions {
     type Error = serde_wasm_bindgen::Error;
 
     fn try_from(value: ReaderOptions) -> std::result::Result<Self, Self::Error> {
-        serde_wasm_bindgen::from_value(value.obj)
+        let schema = Reflect::get(&value.obj, &"schema".into()).unwrap_or(JsValue::UNDEFINED);
+        #[cfg(feature = "async")]
+        let signal = Signal::from_options(&value.obj)?;
+        #[cfg(feature = "async")]
+        let on_progress = Progress::from_options(&value.obj)?;
+        let mut options: Self = serde_wasm_bindgen::from_value(value.obj)?;
+        options.schema = target_schema::parse_schema(schema)?;
+        #[cfg(feature = "async")]
+        {
+            options.signal = signal;
+            options.on_progress = on_progress;
+        }
+        Ok(options)
     }
 }
 
-fn generate_projection_mask<S: AsRef<str>>(
+pub(crate) fn generate_projection_mask<S: AsRef<str>>(
     columns: &[S],
     pq_schema: &SchemaDescriptor,
 ) -> Result<ProjectionMask> {
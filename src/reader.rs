@@ -1,63 +1,175 @@
 use std::sync::Arc;
 
-use crate::error::Result;
-use crate::read_options::JsReaderOptions;
-use arrow_schema::{DataType, FieldRef};
-use arrow_wasm::{Schema, Table};
+use crate::buffer::{ParquetBuffer, ParquetInput};
+use crate::error::{Result, WasmResult};
+use crate::read_options::{JsReaderOptions, ReaderOptions};
+use crate::row_numbers::{RowNumbers, with_row_numbers};
+use crate::type_coercion::cast_batch;
+use arrow::record_batch::RecordBatchReader;
+use arrow_schema::{DataType, FieldRef, SchemaRef};
+use arrow_wasm::{RecordBatch, Schema, Table};
 use bytes::Bytes;
+use js_sys::{Object, Reflect};
 use parquet::arrow::arrow_reader::{
-    ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
+    ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReader,
+    ParquetRecordBatchReaderBuilder,
 };
+use parquet::file::metadata::{PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader};
+use wasm_bindgen::prelude::*;
 
-/// Internal function to read a buffer with Parquet data into a buffer with Arrow IPC Stream data
-pub fn read_parquet(parquet_file: Vec<u8>, options: JsReaderOptions) -> Result<Table> {
-    // Create Parquet reader
-    let cursor: Bytes = parquet_file.into();
-
+/// Create a reader of Arrow record batches from a buffer with Parquet data, along with the row
+/// numbers of its rows if requested
+fn create_reader(
+    cursor: Bytes,
+    options: &JsReaderOptions,
+) -> Result<(ParquetRecordBatchReader, Option<RowNumbers>)> {
     let metadata = ArrowReaderMetadata::load(&cursor, Default::default())?;
-    let metadata = cast_metadata_view_types(&metadata)?;
+    let metadata = options.apply_to_metadata(&metadata)?;
 
-    let mut builder = ParquetRecordBatchReaderBuilder::new_with_metadata(cursor, metadata);
+    let builder = ParquetRecordBatchReaderBuilder::new_with_metadata(cursor, metadata);
+    let (builder, row_numbers) = options.apply_to_builder(builder)?;
 
-    let schema = builder.schema().clone();
+    Ok((builder.build()?, row_numbers))
+}
 
-    if let Some(batch_size) = options.batch_size {
-        builder = builder.with_batch_size(batch_size);
-    }
+/// Internal function to read a buffer with Parquet data into a buffer with Arrow IPC Stream data
+pub fn read_parquet(parquet_file: Bytes, options: JsReaderOptions) -> Result<Table> {
+    let (reader, mut row_numbers) = create_reader(parquet_file, &options)?;
+
+    // Take the schema from the reader so that it reflects any column projection
+    let schema = options.output_schema(&reader.schema());
 
-    if let Some(row_groups) = options.row_groups {
-        builder = builder.with_row_groups(row_groups);
+    let mut batches = vec![];
+
+    for maybe_chunk in reader {
+        let batch = with_row_numbers(maybe_chunk?, row_numbers.as_mut())?;
+        batches.push(cast_batch(batch, &schema)?)
     }
 
-    if let Some(limit) = options.limit {
-        builder = builder.with_limit(limit);
+    Ok(Table::new(schema, batches))
+}
+
+/// A synchronous reader of Parquet data in memory, which decodes one record batch at a time.
+///
+/// Unlike {@linkcode readParquet}, which decodes the entire file into a single {@linkcode Table},
+/// this allows converting and freeing each {@linkcode RecordBatch} before the next one is decoded.
+///
+/// Each call to {@linkcode ParquetReader.next} returns an iterator result, so the reader can be
+/// consumed with `for...of` via `Iterator.from`:
+///
+/// ```js
+/// import { tableFromIPC } from "apache-arrow";
+/// import initWasm, {ParquetReader} from "parquet-wasm";
+///
+/// // Instantiate the WebAssembly context
+/// await initWasm();
+///
+/// const reader = new ParquetReader(parquetUint8Array, { batchSize: 4096 });
+/// for (const batch of Iterator.from(reader)) {
+///   const arrowTable = tableFromIPC(batch.intoIPCStream());
+/// }
+/// ```
+#[wasm_bindgen]
+pub struct ParquetReader {
+    reader: Box<dyn RecordBatchReader>,
+    row_numbers: Option<RowNumbers>,
+    schema: SchemaRef,
+}
+
+#[wasm_bindgen]
+impl ParquetReader {
+    /// Create a reader over a Parquet file in memory.
+    ///
+    /// @param parquet_file Uint8Array or ArrayBuffer containing Parquet data
+    /// @param options Options for reading Parquet data. See {@linkcode readParquet} for the
+    ///     supported keys.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        parquet_file: ParquetInput,
+        options: Option<ReaderOptions>,
+    ) -> WasmResult<ParquetReader> {
+        Self::try_new(parquet_file.to_bytes()?, options)
     }
 
-    if let Some(offset) = options.offset {
-        builder = builder.with_offset(offset);
+    /// Create a reader over a Parquet file in a {@linkcode ParquetBuffer}, without copying it.
+    ///
+    /// The buffer can no longer be written to once it has been read.
+    ///
+    /// @param buffer ParquetBuffer containing Parquet data
+    /// @param options Options for reading Parquet data. See {@linkcode readParquet} for the
+    ///     supported keys.
+    #[wasm_bindgen(js_name = fromParquetBuffer)]
+    pub fn from_parquet_buffer(
+        buffer: &ParquetBuffer,
+        options: Option<ReaderOptions>,
+    ) -> WasmResult<ParquetReader> {
+        Self::try_new(buffer.to_bytes()?, options)
     }
 
-    // Create Arrow reader
-    let reader = builder.build()?;
+    /// The Arrow schema of the record batches returned by this reader.
+    #[wasm_bindgen]
+    pub fn schema(&self) -> Schema {
+        self.schema.clone().into()
+    }
 
-    let mut batches = vec![];
+    /// Decode the next {@linkcode RecordBatch}.
+    ///
+    /// Returns an iterator result, which is `{ done: true }` once all batches have been read.
+    #[allow(clippy::should_implement_trait)]
+    #[wasm_bindgen(unchecked_return_type = "IteratorResult<RecordBatch, undefined>")]
+    pub fn next(&mut self) -> WasmResult<JsValue> {
+        let batch = self
+            .reader
+            .next()
+            .map(|maybe_batch| {
+                let batch = with_row_numbers(maybe_batch?, self.row_numbers.as_mut())?;
+                cast_batch(batch, &self.schema)
+            })
+            .transpose()?;
 
-    for maybe_chunk in reader {
-        batches.push(maybe_chunk?)
+        let result = Object::new();
+        Reflect::set(&result, &"done".into(), &batch.is_none().into())
+            .map_err(|_| JsError::new("Failed to create iterator result"))?;
+        if let Some(batch) = batch {
+            Reflect::set(&result, &"value".into(), &RecordBatch::new(batch).into())
+                .map_err(|_| JsError::new("Failed to create iterator result"))?;
+        }
+        Ok(result.into())
     }
+}
 
-    Ok(Table::new(schema, batches))
+impl ParquetReader {
+    fn try_new(parquet_file: Bytes, options: Option<ReaderOptions>) -> WasmResult<Self> {
+        let options: JsReaderOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let (reader, row_numbers) = create_reader(parquet_file, &options)?;
+        let schema = options.output_schema(&reader.schema());
+        Ok(Self {
+            reader: Box::new(reader),
+            row_numbers,
+            schema,
+        })
+    }
 }
 
 /// Internal function to read a buffer with Parquet data into an Arrow schema
-pub fn read_schema(parquet_file: Vec<u8>) -> Result<Schema> {
+pub fn read_schema(parquet_file: Bytes) -> Result<Schema> {
     // Create Parquet reader
-    let cursor: Bytes = parquet_file.into();
-    let builder = ParquetRecordBatchReaderBuilder::try_new(cursor)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(parquet_file)?;
     let schema = builder.schema().clone();
     Ok(schema.into())
 }
 
+/// Internal function to read the metadata of a buffer with Parquet data
+pub fn read_metadata(parquet_file: Bytes) -> Result<ParquetMetaData> {
+    let metadata = ParquetMetaDataReader::new()
+        .with_page_index_policy(PageIndexPolicy::Optional)
+        .parse_and_finish(&parquet_file)?;
+    Ok(metadata)
+}
+
 /// Cast any view types in the metadata's schema to non-view types
 pub(crate) fn cast_metadata_view_types(
     metadata: &ArrowReaderMetadata,
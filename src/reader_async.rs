@@ -1,30 +1,47 @@
 //! An asynchronous Parquet reader that is able to read and inspect remote files without
 //! downloading them in entirety.
 
+use crate::abort::{Signal, abort_error, abortable, check_aborted};
+use crate::bloom_filter::prune_row_groups_with_bloom_filters;
+use crate::buffer::{ParquetBuffer, ParquetInput};
+use crate::byte_source::{ByteSource, ByteSourceReader};
+use crate::cache::{CacheStats, RangeCache, fetch_missing};
+use crate::coalesce::{IoOptions, coalesce_ranges};
 use crate::common::fetch::{
-    create_reader, get_content_length, range_from_end, range_from_start_and_length,
+    cancelled, create_reader, get_content_length, range_from_end, range_from_start_and_length,
 };
-use crate::error::{Result, WasmResult};
-use crate::read_options::{JsReaderOptions, ReaderOptions};
-use crate::reader::cast_metadata_view_types;
+use crate::error::{ParquetWasmError, Result, WasmResult};
+use crate::file_options::{JsParquetFileOptions, ParquetFileOptions, RequestOptions};
+use crate::filter::{FilterValue, JsFilterValue};
+use crate::metadata::parse_metadata;
+use crate::persistent_cache::PersistentCache;
+use crate::progress::{Progress, report};
+use crate::pruning::prune_row_groups;
+use crate::read_options::{JsReaderOptions, ReaderOptions, generate_projection_mask};
+use crate::retry::{AttemptError, error_for_status, with_retries};
+use crate::row_numbers::{RowNumbers, with_row_numbers};
+use crate::type_coercion::cast_batch;
 use crate::utils;
 use futures::channel::oneshot;
-use futures::future::BoxFuture;
-use object_store::coalesce_ranges;
+use futures::future::{BoxFuture, join_all};
+use futures::stream::LocalBoxStream;
 use parquet::errors::ParquetError;
+use std::future::Future;
 use std::ops::Range;
 use std::sync::Arc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
 use arrow::ipc::writer::StreamWriter;
+use arrow_schema::SchemaRef;
 use arrow_wasm::{RecordBatch, Table};
 use bytes::Bytes;
 use futures::TryStreamExt;
 use futures::{FutureExt, StreamExt, stream};
 use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
 use parquet::arrow::async_reader::{
-    AsyncFileReader, MetadataSuffixFetch, ParquetRecordBatchStream, ParquetRecordBatchStreamBuilder,
+    AsyncFileReader, MetadataFetch, MetadataSuffixFetch, ParquetRecordBatchStream,
+    ParquetRecordBatchStreamBuilder,
 };
 
 use async_compat::{Compat, CompatExt};
@@ -32,38 +49,148 @@ use parquet::file::metadata::{
     FileMetaData, PageIndexPolicy, ParquetMetaData, ParquetMetaDataReader,
 };
 use range_reader::RangedAsyncReader;
-use reqwest::Client;
+use reqwest::header::{CONTENT_RANGE, ETAG};
+use reqwest::{Client, Response, StatusCode};
 
 /// Range requests with a gap less than or equal to this,
 /// will be coalesced into a single request by [`coalesce_ranges`]
 const OBJECT_STORE_COALESCE_DEFAULT: u64 = 1024 * 1024;
 
+/// The default coalesce size of [`JsFileReader`], as reading from a Blob is cheap
+const FILE_COALESCE_DEFAULT: u64 = 1024;
+
+/// Create a stream builder with `options` applied, along with the row numbers of the rows of the
+/// stream if requested
 fn create_builder<T: AsyncFileReader + Unpin + 'static>(
     reader: T,
     meta: &ArrowReaderMetadata,
     options: &JsReaderOptions,
-) -> Result<ParquetRecordBatchStreamBuilder<T>> {
-    // Cast any view types to non-view types
-    let metadata = cast_metadata_view_types(meta)?;
+) -> Result<(ParquetRecordBatchStreamBuilder<T>, Option<RowNumbers>)> {
+    let metadata = options.apply_to_metadata(meta)?;
 
     let builder = ParquetRecordBatchStreamBuilder::new_with_metadata(reader, metadata);
     options.apply_to_builder(builder)
 }
 
-/// An abstraction over either a browser File handle or an ObjectStore instance
-///
-/// This allows exposing a single ParquetFile class to the user.
+/// Load the metadata of a file with `reader`, unless it was passed in `options`.
+async fn load_metadata<T: AsyncFileReader>(
+    reader: &mut T,
+    options: &JsParquetFileOptions,
+) -> Result<ArrowReaderMetadata> {
+    match &options.metadata {
+        Some(metadata) => {
+            let metadata = Arc::new(parse_metadata(metadata)?);
+            Ok(ArrowReaderMetadata::try_new(metadata, Default::default())?)
+        }
+        None => Ok(ArrowReaderMetadata::load_async(reader, Default::default()).await?),
+    }
+}
+
+/// The rows to read from a single row group.
+struct RowGroupRead {
+    row_group: usize,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+/// Split an offset and limit over the rows of all `row_groups` into the offset and limit of each
+/// row group, omitting the row groups that are entirely before the offset or after the limit.
+fn split_offset_limit(
+    meta: &ArrowReaderMetadata,
+    row_groups: Vec<usize>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Vec<RowGroupRead> {
+    let mut offset = offset.unwrap_or(0);
+    let mut limit = limit.unwrap_or(usize::MAX);
+    let mut row_group_reads = vec![];
+    for row_group in row_groups {
+        if limit == 0 {
+            break;
+        }
+        let num_rows = meta.metadata().row_group(row_group).num_rows() as usize;
+        if offset >= num_rows {
+            offset -= num_rows;
+            continue;
+        }
+        let length = (num_rows - offset).min(limit);
+        row_group_reads.push(RowGroupRead {
+            row_group,
+            offset: (offset > 0).then_some(offset),
+            limit: (length < num_rows - offset).then_some(length),
+        });
+        limit -= length;
+        offset = 0;
+    }
+    row_group_reads
+}
+
+/// Skip the first `offset` rows of a stream of batches and stop after `limit` rows.
+pub(crate) fn apply_offset_limit(
+    batches: impl futures::Stream<Item = Result<arrow::record_batch::RecordBatch>> + 'static,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> LocalBoxStream<'static, Result<arrow::record_batch::RecordBatch>> {
+    let state = (offset.unwrap_or(0), limit.unwrap_or(usize::MAX));
+    batches
+        .scan(state, |(offset, limit), maybe_batch| {
+            // Stop before fetching more data once the limit has been reached
+            if *limit == 0 {
+                return futures::future::ready(None);
+            }
+            let result = maybe_batch.map(|batch| {
+                let skip = (*offset).min(batch.num_rows());
+                *offset -= skip;
+                let length = (batch.num_rows() - skip).min(*limit);
+                *limit -= length;
+                batch.slice(skip, length)
+            });
+            futures::future::ready(Some(result))
+        })
+        .try_filter(|batch| futures::future::ready(batch.num_rows() > 0))
+        .boxed_local()
+}
+
+/// An abstraction over either a browser File handle, a remote file, a JS byte source or a buffer
+/// in WebAssembly memory
 #[derive(Clone)]
-enum InnerParquetFile {
+enum FileSource {
     File(JsFileReader),
     Http(HTTPFileReader),
+    Reader(ByteSourceReader),
+    Buffer(BufferReader),
 }
 
-impl AsyncFileReader for InnerParquetFile {
+impl FileSource {
+    /// This reader with its requests cancelled when `signal` is aborted.
+    fn with_signal(self, signal: Option<Signal>) -> Self {
+        match self {
+            Self::File(reader) => Self::File(reader.with_signal(signal)),
+            Self::Http(reader) => Self::Http(reader.with_signal(signal)),
+            Self::Reader(reader) => Self::Reader(reader.with_signal(signal)),
+            // Reads from memory complete immediately
+            Self::Buffer(reader) => Self::Buffer(reader),
+        }
+    }
+
+    /// This reader with the bytes it requests and receives reported to `progress`.
+    fn with_progress(self, progress: Option<Progress>) -> Self {
+        match self {
+            Self::File(reader) => Self::File(reader.with_progress(progress)),
+            Self::Http(reader) => Self::Http(reader.with_progress(progress)),
+            Self::Reader(reader) => Self::Reader(reader.with_progress(progress)),
+            Self::Buffer(reader) => Self::Buffer(reader.with_progress(progress)),
+        }
+    }
+}
+
+impl AsyncFileReader for FileSource {
     fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
         match self {
             Self::File(reader) => reader.get_bytes(range),
             Self::Http(reader) => reader.get_bytes(range),
+            Self::Reader(reader) => reader.get_bytes(range),
+            Self::Buffer(reader) => reader.get_bytes(range),
         }
     }
 
@@ -74,6 +201,8 @@ impl AsyncFileReader for InnerParquetFile {
         match self {
             Self::File(reader) => reader.get_byte_ranges(ranges),
             Self::Http(reader) => reader.get_byte_ranges(ranges),
+            Self::Reader(reader) => reader.get_byte_ranges(ranges),
+            Self::Buffer(reader) => reader.get_byte_ranges(ranges),
         }
     }
 
@@ -84,28 +213,109 @@ impl AsyncFileReader for InnerParquetFile {
         match self {
             Self::File(reader) => reader.get_metadata(options),
             Self::Http(reader) => reader.get_metadata(options),
+            Self::Reader(reader) => reader.get_metadata(options),
+            Self::Buffer(reader) => reader.get_metadata(options),
         }
     }
 }
 
+/// The source of a file, along with the cache of the byte ranges fetched from it
+///
+/// This allows exposing a single ParquetFile class to the user.
+#[derive(Clone)]
+struct InnerParquetFile {
+    source: FileSource,
+    cache: Option<Arc<RangeCache>>,
+}
+
+impl InnerParquetFile {
+    /// Read from `source`, caching up to `cache_size` bytes of the ranges fetched from it.
+    fn new(source: FileSource, cache_size: Option<u64>) -> Self {
+        let cache = cache_size
+            .filter(|size| *size > 0)
+            .map(|size| Arc::new(RangeCache::new(size)));
+        Self { source, cache }
+    }
+
+    /// This reader with its requests cancelled when `signal` is aborted.
+    fn with_signal(self, signal: Option<Signal>) -> Self {
+        Self {
+            source: self.source.with_signal(signal),
+            ..self
+        }
+    }
+
+    /// This reader with the bytes it requests and receives reported to `progress`.
+    fn with_progress(self, progress: Option<Progress>) -> Self {
+        Self {
+            source: self.source.with_progress(progress),
+            ..self
+        }
+    }
+}
+
+impl AsyncFileReader for InnerParquetFile {
+    fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        async move {
+            let mut bytes = self.get_byte_ranges(vec![range]).await?;
+            Ok(bytes.pop().unwrap_or_default())
+        }
+        .boxed()
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<u64>>,
+    ) -> BoxFuture<'_, parquet::errors::Result<Vec<Bytes>>> {
+        let Some(cache) = self.cache.clone() else {
+            return self.source.get_byte_ranges(ranges);
+        };
+        async move {
+            let cached = ranges.iter().map(|range| cache.get(range)).collect();
+            fetch_missing(
+                ranges,
+                cached,
+                |missing| self.source.get_byte_ranges(missing),
+                |range, bytes| cache.insert(range, bytes.clone()),
+            )
+            .await
+        }
+        .boxed()
+    }
+
+    fn get_metadata<'a>(
+        &'a mut self,
+        options: Option<&'a ArrowReaderOptions>,
+    ) -> BoxFuture<'a, parquet::errors::Result<Arc<ParquetMetaData>>> {
+        self.source.get_metadata(options)
+    }
+}
+
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct ParquetFile {
     reader: InnerParquetFile,
     meta: ArrowReaderMetadata,
+    /// The number of row groups to fetch concurrently, unless set by the read options
+    concurrency: Option<usize>,
 }
 
 #[wasm_bindgen]
 impl ParquetFile {
     /// Construct a ParquetFile from a new URL.
+    ///
+    /// @param url The URL of the file
+    /// @param options See {@linkcode ParquetFileOptions}
     #[wasm_bindgen(js_name = fromUrl)]
-    pub async fn from_url(url: String) -> WasmResult<ParquetFile> {
-        let client = Client::new();
-        let mut reader = HTTPFileReader::new(url, client, OBJECT_STORE_COALESCE_DEFAULT);
-        let meta = ArrowReaderMetadata::load_async(&mut reader, Default::default()).await?;
-        Ok(Self {
-            reader: InnerParquetFile::Http(reader),
-            meta,
-        })
+    pub async fn from_url(
+        url: String,
+        options: Option<ParquetFileOptions>,
+    ) -> WasmResult<ParquetFile> {
+        let options: JsParquetFileOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self::open_url(url, options).await?)
     }
 
     /// Construct a ParquetFile from a new [Blob] or [File] handle.
@@ -115,16 +325,96 @@ impl ParquetFile {
     ///
     /// Safety: Do not use this in a multi-threaded environment,
     /// (transitively depends on `!Send` `web_sys::Blob`)
+    ///
+    /// @param handle The Blob or File to read
+    /// @param options See {@linkcode ParquetFileOptions}. The options of HTTP requests are
+    ///     ignored.
     #[wasm_bindgen(js_name = fromFile)]
-    pub async fn from_file(handle: web_sys::Blob) -> WasmResult<ParquetFile> {
-        let mut reader = JsFileReader::new(handle, 1024);
-        let meta = ArrowReaderMetadata::load_async(&mut reader, Default::default()).await?;
+    pub async fn from_file(
+        handle: web_sys::Blob,
+        options: Option<ParquetFileOptions>,
+    ) -> WasmResult<ParquetFile> {
+        let options: JsParquetFileOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let reader = JsFileReader::new(handle, FILE_COALESCE_DEFAULT)
+            .with_io_options(options.io_options(FILE_COALESCE_DEFAULT));
+        let signal = options.signal.as_ref();
+        check_aborted(signal)?;
+        let mut loading_reader = reader.clone().with_signal(signal.cloned());
+        let meta = load_metadata(&mut loading_reader, &options)
+            .await
+            .map_err(|err| abort_error(signal, err))?;
+        Ok(Self {
+            reader: InnerParquetFile::new(FileSource::File(reader), options.cache_size),
+            meta,
+            concurrency: options.concurrency,
+        })
+    }
+
+    /// Construct a ParquetFile from a byte source implemented in JS, e.g. backed by IndexedDB or
+    /// the origin private file system.
+    ///
+    /// @param source An object with the `size` of the file and a `getBytes(start, end)` method.
+    ///     See {@linkcode ByteSource}
+    /// @param options See {@linkcode ParquetFileOptions}. The options of HTTP requests are
+    ///     ignored.
+    #[wasm_bindgen(js_name = fromReader)]
+    pub async fn from_reader(
+        source: ByteSource,
+        options: Option<ParquetFileOptions>,
+    ) -> WasmResult<ParquetFile> {
+        let options: JsParquetFileOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let reader = ByteSourceReader::try_new(source, OBJECT_STORE_COALESCE_DEFAULT)?
+            .with_io_options(options.io_options(OBJECT_STORE_COALESCE_DEFAULT));
+        let signal = options.signal.as_ref();
+        check_aborted(signal)?;
+        let mut loading_reader = reader.clone().with_signal(signal.cloned());
+        let meta = load_metadata(&mut loading_reader, &options)
+            .await
+            .map_err(|err| abort_error(signal, err))?;
         Ok(Self {
-            reader: InnerParquetFile::File(reader),
+            reader: InnerParquetFile::new(FileSource::Reader(reader), options.cache_size),
             meta,
+            concurrency: options.concurrency,
         })
     }
 
+    /// Construct a ParquetFile from Parquet data in memory.
+    ///
+    /// A `Uint8Array` is copied into WebAssembly memory once and shared by all reads of the file.
+    ///
+    /// @param buffer The Parquet file, as a `Uint8Array` or an `ArrayBuffer`
+    /// @param options See {@linkcode ParquetFileOptions}. The options of requests and of the
+    ///     range cache are ignored.
+    #[wasm_bindgen(js_name = fromBuffer)]
+    pub async fn from_buffer(
+        buffer: ParquetInput,
+        options: Option<ParquetFileOptions>,
+    ) -> WasmResult<ParquetFile> {
+        Self::from_bytes(buffer.to_bytes()?, options).await
+    }
+
+    /// Construct a ParquetFile from Parquet data in a {@linkcode ParquetBuffer}, without copying
+    /// it.
+    ///
+    /// The buffer can no longer be written to once it has been read.
+    ///
+    /// @param buffer ParquetBuffer containing the Parquet file
+    /// @param options See {@linkcode ParquetFileOptions}. The options of requests and of the
+    ///     range cache are ignored.
+    #[wasm_bindgen(js_name = fromParquetBuffer)]
+    pub async fn from_parquet_buffer(
+        buffer: &ParquetBuffer,
+        options: Option<ParquetFileOptions>,
+    ) -> WasmResult<ParquetFile> {
+        Self::from_bytes(buffer.to_bytes()?, options).await
+    }
+
     #[wasm_bindgen]
     pub fn metadata(&self) -> WasmResult<crate::metadata::ParquetMetaData> {
         Ok(self.meta.metadata().as_ref().to_owned().into())
@@ -147,19 +437,58 @@ impl ParquetFile {
     ///    - `limit`: Provide a limit to the number of rows to be read.
     ///    - `offset`: Provide an offset to skip over the given number of rows.
     ///    - `columns`: The column names from the file to read.
+    ///    - `filter`: Only read rows matching this {@linkcode FilterExpression}.
+    ///    - `typeCoercion`: Convert the Arrow types read from the file. See {@linkcode TypeCoercion}.
+    ///    - `schema`: Read columns as the types of the fields with the same name in this schema,
+    ///           given as an Arrow IPC stream or a {@linkcode SchemaDescription}.
+    ///    - `skipArrowMetadata`: Ignore the Arrow schema stored in the file by the writer.
+    ///    - `rowNumberColumn`: Append a column with this name holding the 0-based position of each
+    ///           row in the file.
+    ///    - `signal`: An `AbortSignal` to cancel the read with. In-flight requests are aborted
+    ///           and the promise rejects.
+    ///    - `onProgress`: A callback receiving the {@linkcode ReadProgress} of the read.
     #[wasm_bindgen]
     pub async fn read(&self, options: Option<ReaderOptions>) -> WasmResult<Table> {
-        let options = options
+        let mut options: JsReaderOptions = options
             .map(|x| x.try_into())
             .transpose()?
             .unwrap_or_default();
-        let builder = create_builder(self.reader.clone(), &self.meta, &options)?;
+        let signal = options.signal.clone();
+        let read = async {
+            check_aborted(options.signal.as_ref())?;
+            let row_groups = self.matching_row_groups(&options).await?;
+            let num_row_groups = row_groups.len();
+            report(options.on_progress.as_ref(), |progress| {
+                progress.row_groups_total += num_row_groups
+            });
+            options.row_groups = Some(row_groups);
+            let (builder, mut row_numbers) =
+                create_builder(self.reader_for(&options), &self.meta, &options)?;
 
-        let schema = builder.schema().clone();
-        let stream = builder.build()?;
-        let batches = stream.try_collect::<Vec<_>>().await.unwrap();
+            let stream = builder.build()?;
+            // Take the schema from the stream so that it reflects any column projection
+            let schema = options.output_schema(stream.schema());
+            let progress = options.on_progress.clone();
+            let batches = stream
+                .map(|maybe_batch| {
+                    let batch = with_row_numbers(maybe_batch?, row_numbers.as_mut())?;
+                    report(progress.as_ref(), |progress| {
+                        progress.rows_decoded += batch.num_rows()
+                    });
+                    Ok::<_, ParquetWasmError>(cast_batch(batch, &schema)?)
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+            // All row groups are decoded by a single stream, so they complete together
+            report(progress.as_ref(), |progress| {
+                progress.row_groups_completed += num_row_groups
+            });
+            Ok::<_, ParquetWasmError>(Table::new(schema, batches))
+        };
 
-        Ok(Table::new(schema, batches))
+        Ok(read
+            .await
+            .map_err(|err| abort_error(signal.as_ref(), err))?)
     }
 
     /// Create a readable stream of record batches.
@@ -176,7 +505,18 @@ impl ParquetFile {
     ///    - `limit`: Provide a limit to the number of rows to be read.
     ///    - `offset`: Provide an offset to skip over the given number of rows.
     ///    - `columns`: The column names from the file to read.
-    ///    - `concurrency`: The number of concurrent requests to make
+    ///    - `filter`: Only read rows matching this {@linkcode FilterExpression}.
+    ///    - `typeCoercion`: Convert the Arrow types read from the file. See {@linkcode TypeCoercion}.
+    ///    - `schema`: Read columns as the types of the fields with the same name in this schema,
+    ///           given as an Arrow IPC stream or a {@linkcode SchemaDescription}.
+    ///    - `skipArrowMetadata`: Ignore the Arrow schema stored in the file by the writer.
+    ///    - `rowNumberColumn`: Append a column with this name holding the 0-based position of each
+    ///           row in the file.
+    ///    - `concurrency`: The number of row groups to fetch concurrently. Defaults to the
+    ///           `concurrency` the file was opened with, or 1.
+    ///    - `signal`: An `AbortSignal` to cancel the stream with. In-flight requests are aborted
+    ///           and the stream errors.
+    ///    - `onProgress`: A callback receiving the {@linkcode ReadProgress} of the stream.
     #[wasm_bindgen]
     pub async fn stream(
         &self,
@@ -187,26 +527,272 @@ impl ParquetFile {
             .transpose()?
             .unwrap_or_default();
 
-        let concurrency = options.concurrency.unwrap_or_default().max(1);
-        let row_groups = options
-            .row_groups
+        let signal = options.signal.clone();
+        let out_stream = self
+            .record_batch_stream(options)
+            .await
+            .map_err(|err| abort_error(signal.as_ref(), err))?
+            .map(move |maybe_record_batch| match maybe_record_batch {
+                Ok(record_batch) => Ok(RecordBatch::new(record_batch).into()),
+                // Error the stream instead of panicking, so that the module remains usable
+                Err(err) => Err(JsError::from(abort_error(signal.as_ref(), err)).into()),
+            });
+        Ok(wasm_streams::ReadableStream::from_stream(out_stream).into_raw())
+    }
+
+    /// Find the row groups that may contain a value, using the bloom filters in the file.
+    ///
+    /// Only the bloom filter of the column in each row group is fetched, not the column data.
+    /// Row groups without a bloom filter for the column are always included.
+    ///
+    /// @param column The name of the column, using `.` to separate nested fields.
+    /// @param value The value to look up.
+    /// @param rowGroups Only check these row group indexes. Defaults to all row groups.
+    /// @returns The indexes of the row groups that may contain the value.
+    #[wasm_bindgen(js_name = bloomFilterContains)]
+    pub async fn bloom_filter_contains(
+        &self,
+        column: String,
+        value: JsFilterValue,
+        #[wasm_bindgen(js_name = rowGroups)] row_groups: Option<Vec<usize>>,
+    ) -> WasmResult<Vec<usize>> {
+        let value: FilterValue = value.try_into()?;
+        let row_groups =
+            row_groups.unwrap_or_else(|| (0..self.meta.metadata().num_row_groups()).collect());
+        Ok(prune_row_groups_with_bloom_filters(
+            self.reader.clone(),
+            &self.meta,
+            &column,
+            &[value],
+            row_groups,
+            self.concurrency.unwrap_or_default().max(1),
+        )
+        .await?)
+    }
+
+    /// The hit and miss counters and the size of the byte range cache of the file.
+    ///
+    /// The cache is enabled with the `cacheSize` option of {@linkcode ParquetFile.fromUrl} or
+    /// {@linkcode ParquetFile.fromFile}. Without it, all statistics are 0.
+    #[wasm_bindgen(js_name = cacheStats)]
+    pub fn cache_stats(&self) -> WasmResult<CacheStats> {
+        let stats = self
+            .reader
+            .cache
+            .as_ref()
+            .map(|cache| cache.stats())
+            .unwrap_or_default();
+        Ok(serde_wasm_bindgen::to_value(&stats)?.unchecked_into())
+    }
+
+    /// Remove all byte ranges from the cache of the file, so that later reads fetch them again.
+    #[wasm_bindgen(js_name = clearCache)]
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.reader.cache {
+            cache.clear();
+        }
+    }
+}
+
+impl ParquetFile {
+    /// Open the file at `url`, fetching its metadata unless it is passed in `options`.
+    pub(crate) async fn open_url(url: String, options: JsParquetFileOptions) -> Result<Self> {
+        let client = Client::new();
+        let mut reader = HTTPFileReader::new(url.clone(), client, OBJECT_STORE_COALESCE_DEFAULT)
+            .with_io_options(options.io_options(OBJECT_STORE_COALESCE_DEFAULT))
+            .with_request_options(options.request_options())
+            .with_footer_prefetch(options.footer_prefetch_bytes);
+        // The signal only applies to opening the file, so it is not kept by the reader
+        let signal = options.signal.as_ref();
+        check_aborted(signal)?;
+        if let Some(adapter) = &options.cache_adapter {
+            // Cached ranges are stored under the ETag, so it is needed before the footer can be
+            // read from the cache, and is fetched even if the metadata was passed in `options`
+            let (etag, file_size) = reader
+                .clone()
+                .with_signal(signal.cloned())
+                .fetch_file_info()
+                .await
+                .map_err(|err| abort_error(signal, err))?;
+            let persistent_cache =
+                etag.map(|etag| PersistentCache::new(adapter.clone(), url, etag));
+            reader = reader
+                .with_persistent_cache(persistent_cache)
+                .with_file_size(file_size);
+        }
+        let mut loading_reader = reader.clone().with_signal(signal.cloned());
+        let meta = load_metadata(&mut loading_reader, &options)
+            .await
+            .map_err(|err| abort_error(signal, err))?;
+        Ok(Self {
+            reader: InnerParquetFile::new(FileSource::Http(reader), options.cache_size),
+            meta,
+            concurrency: options.concurrency,
+        })
+    }
+
+    /// Open Parquet data in memory, parsing the options of `fromBuffer`.
+    async fn from_bytes(bytes: Bytes, options: Option<ParquetFileOptions>) -> WasmResult<Self> {
+        let options: JsParquetFileOptions = options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default();
+        let mut reader = BufferReader::new(bytes);
+        check_aborted(options.signal.as_ref())?;
+        let meta = load_metadata(&mut reader, &options).await?;
+        Ok(Self {
+            // The data is already in memory, so there is nothing to cache
+            reader: InnerParquetFile::new(FileSource::Buffer(reader), None),
+            meta,
+            concurrency: options.concurrency,
+        })
+    }
+
+    /// Whether the file contains the column, using `.` to separate nested fields.
+    pub(crate) fn has_column(&self, column: &str) -> bool {
+        generate_projection_mask(&[column], self.meta.parquet_schema()).is_ok()
+    }
+
+    /// The reader to fetch the data selected by `options` with.
+    fn reader_for(&self, options: &JsReaderOptions) -> InnerParquetFile {
+        self.reader
             .clone()
-            .unwrap_or_else(|| (0..self.meta.metadata().num_row_groups()).collect());
-        let reader = self.reader.clone();
+            .with_signal(options.signal.clone())
+            .with_progress(options.on_progress.clone())
+    }
+
+    /// The number of row groups to fetch concurrently when reading with `options`.
+    fn concurrency_for(&self, options: &JsReaderOptions) -> usize {
+        options
+            .concurrency
+            .or(self.concurrency)
+            .unwrap_or_default()
+            .max(1)
+    }
+
+    /// The schema of the record batches read with `options`.
+    pub(crate) fn output_schema(&self, options: &JsReaderOptions) -> Result<SchemaRef> {
+        // Building the stream does not fetch any data
+        let (builder, _) = create_builder(self.reader.clone(), &self.meta, options)?;
+        let stream = builder.build()?;
+        Ok(options.output_schema(stream.schema()))
+    }
+
+    /// Read the record batches selected by `options`, fetching up to `options.concurrency` row
+    /// groups concurrently.
+    ///
+    /// The offset and limit of `options` apply to the file as a whole.
+    pub(crate) async fn record_batch_stream(
+        &self,
+        options: JsReaderOptions,
+    ) -> Result<LocalBoxStream<'static, Result<arrow::record_batch::RecordBatch>>> {
+        check_aborted(options.signal.as_ref())?;
+        let concurrency = self.concurrency_for(&options);
+        let row_groups = self.matching_row_groups(&options).await?;
+        let reader = self.reader_for(&options);
         let meta = self.meta.clone();
 
-        let buffered_stream = stream::iter(row_groups.into_iter().map(move |i| {
-            let builder = create_builder(reader.clone(), &meta.clone(), &options.clone())
-                .unwrap()
-                .with_row_groups(vec![i]);
-            builder.build().unwrap().try_collect::<Vec<_>>()
+        // Without a filter, the number of rows read from each row group is known up front, so the
+        // offset and limit can be split between row groups and the row groups outside of them are
+        // never fetched. With a filter, they are applied to the rows returned by all row groups.
+        let (row_group_reads, global_offset_limit) = if options.filter.is_none() {
+            let row_group_reads =
+                split_offset_limit(&meta, row_groups, options.offset, options.limit);
+            (row_group_reads, None)
+        } else {
+            let row_group_reads = row_groups
+                .into_iter()
+                .map(|row_group| RowGroupRead {
+                    row_group,
+                    offset: None,
+                    // No row group needs to return more rows than the offset plus the limit
+                    limit: options
+                        .limit
+                        .map(|limit| limit.saturating_add(options.offset.unwrap_or(0))),
+                })
+                .collect();
+            (row_group_reads, Some((options.offset, options.limit)))
+        };
+        // Add to the total, as a dataset reports the progress of all its files together
+        report(options.on_progress.as_ref(), |progress| {
+            progress.row_groups_total += row_group_reads.len()
+        });
+
+        let buffered_stream = stream::iter(row_group_reads.into_iter().map(move |read| {
+            // Restrict the options to this row group, so that any row selection derived from the
+            // options is computed for this row group only
+            let row_group_options = JsReaderOptions {
+                row_groups: Some(vec![read.row_group]),
+                offset: read.offset,
+                limit: read.limit,
+                ..options.clone()
+            };
+            let reader = reader.clone();
+            let meta = meta.clone();
+            let options = options.clone();
+            async move {
+                let (builder, mut row_numbers) = create_builder(reader, &meta, &row_group_options)?;
+                let stream = builder.build()?;
+                let schema = options.output_schema(stream.schema());
+                let progress = options.on_progress;
+                let batches = stream
+                    .map(|maybe_batch| {
+                        let batch = with_row_numbers(maybe_batch?, row_numbers.as_mut())?;
+                        report(progress.as_ref(), |progress| {
+                            progress.rows_decoded += batch.num_rows()
+                        });
+                        Ok::<_, ParquetWasmError>(cast_batch(batch, &schema)?)
+                    })
+                    .try_collect::<Vec<_>>()
+                    .await?;
+                report(progress.as_ref(), |progress| {
+                    progress.row_groups_completed += 1
+                });
+                Ok::<_, ParquetWasmError>(batches)
+            }
         }))
         .buffered(concurrency);
-        let out_stream = buffered_stream.flat_map(|maybe_record_batches| {
-            stream::iter(maybe_record_batches.unwrap())
-                .map(|record_batch| Ok(RecordBatch::new(record_batch).into()))
-        });
-        Ok(wasm_streams::ReadableStream::from_stream(out_stream).into_raw())
+        let out_stream = buffered_stream
+            .map_ok(|record_batches| stream::iter(record_batches.into_iter().map(Ok)))
+            .try_flatten();
+        Ok(match global_offset_limit {
+            Some((offset, limit)) => apply_offset_limit(out_stream, offset, limit),
+            None => out_stream.boxed_local(),
+        })
+    }
+
+    /// The row groups selected by `options`, excluding those that cannot contain rows matching
+    /// its filter according to the column statistics or the bloom filters of the file.
+    async fn matching_row_groups(&self, options: &JsReaderOptions) -> Result<Vec<usize>> {
+        let mut row_groups = options.row_groups_or_all(self.meta.metadata().num_row_groups())?;
+        let Some(filter) = &options.filter else {
+            return Ok(row_groups);
+        };
+
+        row_groups = prune_row_groups(
+            filter,
+            self.meta.schema(),
+            self.meta.parquet_schema(),
+            self.meta.metadata(),
+            row_groups,
+        );
+        for (column, values) in filter.equality_conjuncts() {
+            // With at most one row group left, fetching its bloom filters costs an extra request
+            // that rarely saves one
+            if row_groups.len() <= 1 {
+                break;
+            }
+            row_groups = prune_row_groups_with_bloom_filters(
+                self.reader_for(options),
+                &self.meta,
+                column,
+                &values,
+                row_groups,
+                self.concurrency_for(options),
+            )
+            .await?;
+        }
+        Ok(row_groups)
     }
 }
 
@@ -214,7 +800,14 @@ impl ParquetFile {
 pub struct HTTPFileReader {
     url: String,
     client: Client,
-    coalesce_byte_size: u64,
+    io_options: IoOptions,
+    request_options: Arc<RequestOptions>,
+    persistent_cache: Option<Arc<PersistentCache>>,
+    /// The size of the file, if it is known before the metadata is loaded
+    file_size: Option<u64>,
+    footer_prefetch_bytes: Option<usize>,
+    signal: Option<Signal>,
+    progress: Option<Progress>,
 }
 
 impl HTTPFileReader {
@@ -222,7 +815,131 @@ impl HTTPFileReader {
         Self {
             url,
             client,
-            coalesce_byte_size,
+            io_options: IoOptions::new(coalesce_byte_size),
+            request_options: Default::default(),
+            persistent_cache: None,
+            file_size: None,
+            footer_prefetch_bytes: None,
+            signal: None,
+            progress: None,
+        }
+    }
+
+    /// Coalesce, split and parallelize requests according to `io_options`.
+    pub fn with_io_options(self, io_options: IoOptions) -> Self {
+        Self { io_options, ..self }
+    }
+
+    /// Make every request for the file with the headers and credentials of `request_options`.
+    pub fn with_request_options(self, request_options: Arc<RequestOptions>) -> Self {
+        Self {
+            request_options,
+            ..self
+        }
+    }
+
+    /// Read the ranges of the file from `persistent_cache` before requesting them, and store the
+    /// requested ranges in it.
+    pub fn with_persistent_cache(self, persistent_cache: Option<Arc<PersistentCache>>) -> Self {
+        Self {
+            persistent_cache,
+            ..self
+        }
+    }
+
+    /// Use the known size of the file when loading its metadata.
+    pub fn with_file_size(self, file_size: Option<u64>) -> Self {
+        Self { file_size, ..self }
+    }
+
+    /// Fetch the last `footer_prefetch_bytes` of the file with a single request when loading its
+    /// metadata, and decode the metadata and page index from them if they fit.
+    pub fn with_footer_prefetch(self, footer_prefetch_bytes: Option<usize>) -> Self {
+        Self {
+            footer_prefetch_bytes,
+            ..self
+        }
+    }
+
+    /// Abort in-flight requests and fail new ones once `signal` is aborted.
+    pub fn with_signal(self, signal: Option<Signal>) -> Self {
+        Self { signal, ..self }
+    }
+
+    /// Report the bytes requested and received to `progress`.
+    pub fn with_progress(self, progress: Option<Progress>) -> Self {
+        Self { progress, ..self }
+    }
+
+    /// The `ETag` and the size of the file, found with a request for its first byte.
+    pub async fn fetch_file_info(&self) -> Result<(Option<String>, Option<u64>)> {
+        self.fetch(range_from_start_and_length(0, 1), |response| async move {
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|etag| etag.to_str().ok())
+                .map(String::from);
+            Ok((etag, content_range_size(&response)))
+        })
+        .await
+    }
+
+    /// Fetch the last `suffix` bytes of the file, along with the size of the file if the server
+    /// exposes it.
+    async fn fetch_suffix_with_size(&self, suffix: usize) -> Result<(Bytes, Option<u64>)> {
+        if let Some(file_size) = self.file_size {
+            let start = file_size.saturating_sub(suffix as u64);
+            let bytes = self.clone().get_bytes(start..file_size).await?;
+            return Ok((bytes, Some(file_size)));
+        }
+        self.fetch(range_from_end(suffix), |response| async move {
+            // A server ignoring the range responds with the whole file
+            let whole_file = response.status() == StatusCode::OK;
+            let size = content_range_size(&response);
+            let bytes = response.bytes().await?;
+            let size = size.or(whole_file.then_some(bytes.len() as u64));
+            Ok((bytes, size))
+        })
+        .await
+    }
+
+    /// Request a range of the file, given as the value of a `Range` header.
+    async fn fetch_range(&self, range_str: String) -> Result<Bytes> {
+        self.fetch(range_str, |response| response.bytes()).await
+    }
+
+    /// Request a range of the file, given as the value of a `Range` header, and read the
+    /// successful response with `read`.
+    async fn fetch<T, F, Fut>(&self, range_str: String, read: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: Fn(Response) -> Fut + 'static,
+        Fut: Future<Output = std::result::Result<T, reqwest::Error>>,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let reader = self.clone();
+        spawn_local(async move {
+            // The request is prepared again for every attempt, e.g. to refresh an expired token
+            let (options, client, url) = (&reader.request_options, &reader.client, &reader.url);
+            let (range_str, read) = (&range_str, &read);
+            let request = with_retries(&options.retry, move || async move {
+                let request = options
+                    .range_request(client, url, range_str)
+                    .await
+                    .map_err(AttemptError::permanent)?;
+                let response = error_for_status(request.send().await?)?;
+                Ok::<_, AttemptError>(read(response).await?)
+            });
+            // Dropping the request when the signal is aborted aborts the underlying fetch. The
+            // error is converted to a type that can be sent between tasks.
+            let result = abortable(reader.signal.as_ref(), request)
+                .await
+                .map(|result| result.map_err(ParquetError::from));
+            let _ = sender.send(result);
+        });
+        match receiver.await.map_err(cancelled)? {
+            Some(result) => Ok(result?),
+            None => Err(ParquetWasmError::Aborted),
         }
     }
 }
@@ -231,18 +948,15 @@ impl MetadataSuffixFetch for &mut HTTPFileReader {
     fn fetch_suffix(&mut self, suffix: usize) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
         async move {
             let range_str = range_from_end(suffix);
-
-            // Map reqwest error to parquet error
-            // let map_err = |err| parquet::errors::ParquetError::External(Box::new(err));
-
-            let bytes = make_range_request_with_client(
-                self.url.to_string(),
-                self.client.clone(),
-                range_str,
-            )
-            .await
-            .unwrap();
-
+            if let Some(cache) = &self.persistent_cache {
+                if let Some(bytes) = cache.get(&range_str, None).await {
+                    return Ok(bytes);
+                }
+            }
+            let bytes = self.fetch_range(range_str.clone()).await?;
+            if let Some(cache) = &self.persistent_cache {
+                cache.put(&range_str, bytes.clone());
+            }
             Ok(bytes)
         }
         .boxed()
@@ -250,25 +964,29 @@ impl MetadataSuffixFetch for &mut HTTPFileReader {
 }
 
 async fn get_bytes_http(
-    url: String,
-    client: Client,
+    reader: HTTPFileReader,
     range: Range<u64>,
 ) -> parquet::errors::Result<Bytes> {
     let range_str = range_from_start_and_length(range.start, range.end - range.start);
-
-    // Map reqwest error to parquet error
-    // let map_err = |err| parquet::errors::ParquetError::External(Box::new(err));
-
-    let bytes = make_range_request_with_client(url, client, range_str)
-        .await
-        .unwrap();
+    report(reader.progress.as_ref(), |progress| {
+        progress.bytes_requested += range.end - range.start
+    });
+    let bytes = reader.fetch_range(range_str).await?;
+    report(reader.progress.as_ref(), |progress| {
+        progress.bytes_received += bytes.len() as u64
+    });
 
     Ok(bytes)
 }
 
 impl AsyncFileReader for HTTPFileReader {
     fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
-        get_bytes_http(self.url.clone(), self.client.clone(), range).boxed()
+        async move {
+            // Split the range if it exceeds the maximum request size
+            let mut bytes = self.get_byte_ranges(vec![range]).await?;
+            Ok(bytes.pop().unwrap_or_default())
+        }
+        .boxed()
     }
 
     fn get_byte_ranges(
@@ -276,10 +994,42 @@ impl AsyncFileReader for HTTPFileReader {
         ranges: Vec<Range<u64>>,
     ) -> BoxFuture<'_, parquet::errors::Result<Vec<Bytes>>> {
         async move {
-            coalesce_ranges(
-                &ranges,
-                |range| get_bytes_http(self.url.clone(), self.client.clone(), range),
-                self.coalesce_byte_size,
+            let Some(cache) = self.persistent_cache.clone() else {
+                return coalesce_ranges(
+                    &ranges,
+                    |range| get_bytes_http(self.clone(), range),
+                    &self.io_options,
+                )
+                .await;
+            };
+            let range_strs = ranges
+                .iter()
+                .map(|range| range_from_start_and_length(range.start, range.end - range.start))
+                .collect::<Vec<_>>();
+            let cached = join_all(
+                ranges
+                    .iter()
+                    .zip(&range_strs)
+                    .map(|(range, range_str)| cache.get(range_str, Some(range.end - range.start))),
+            )
+            .await;
+            let reader = self.clone();
+            fetch_missing(
+                ranges,
+                cached,
+                |missing| async move {
+                    coalesce_ranges(
+                        &missing,
+                        |range| get_bytes_http(reader.clone(), range),
+                        &reader.io_options,
+                    )
+                    .await
+                },
+                |range, bytes| {
+                    let range_str =
+                        range_from_start_and_length(range.start, range.end - range.start);
+                    cache.put(&range_str, bytes.clone())
+                },
             )
             .await
         }
@@ -291,16 +1041,86 @@ impl AsyncFileReader for HTTPFileReader {
         _options: Option<&'a ArrowReaderOptions>,
     ) -> BoxFuture<'a, parquet::errors::Result<Arc<ParquetMetaData>>> {
         async move {
-            let metadata = ParquetMetaDataReader::new()
-                .with_page_index_policy(PageIndexPolicy::Optional)
-                .load_via_suffix_and_finish(self)
-                .await?;
+            let metadata_reader =
+                ParquetMetaDataReader::new().with_page_index_policy(PageIndexPolicy::Optional);
+            let Some(prefetch) = self.footer_prefetch_bytes else {
+                return Ok(Arc::new(
+                    metadata_reader.load_via_suffix_and_finish(self).await?,
+                ));
+            };
+            let (suffix, file_size) = self.fetch_suffix_with_size(prefetch).await?;
+            let metadata_reader = metadata_reader.with_prefetch_hint(Some(prefetch));
+            let prefetched = PrefetchedSuffix {
+                reader: self,
+                suffix,
+                file_size,
+            };
+            // Without the size of the file, e.g. if CORS does not expose the `Content-Range`
+            // header, the page index cannot be located in the suffix and is fetched separately
+            let metadata = match file_size {
+                Some(file_size) => {
+                    metadata_reader
+                        .load_and_finish(prefetched, file_size)
+                        .await?
+                }
+                None => {
+                    metadata_reader
+                        .load_via_suffix_and_finish(prefetched)
+                        .await?
+                }
+            };
             Ok(Arc::new(metadata))
         }
         .boxed()
     }
 }
 
+/// The suffix of a file fetched up front, serving the requests for the metadata it contains
+struct PrefetchedSuffix<'a> {
+    reader: &'a mut HTTPFileReader,
+    suffix: Bytes,
+    file_size: Option<u64>,
+}
+
+impl MetadataFetch for PrefetchedSuffix<'_> {
+    fn fetch(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        // A server may return a longer suffix than the reported file size, in which case the
+        // suffix cannot be located in the file
+        let suffix_start = self.file_size.and_then(|file_size| {
+            Some((file_size.checked_sub(self.suffix.len() as u64)?, file_size))
+        });
+        if let Some((start, file_size)) = suffix_start {
+            if range.start >= start && range.end <= file_size {
+                let bytes = self
+                    .suffix
+                    .slice((range.start - start) as usize..(range.end - start) as usize);
+                return futures::future::ready(Ok(bytes)).boxed();
+            }
+        }
+        self.reader.get_bytes(range)
+    }
+}
+
+impl MetadataSuffixFetch for PrefetchedSuffix<'_> {
+    fn fetch_suffix(&mut self, suffix: usize) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        if suffix <= self.suffix.len() {
+            let bytes = self.suffix.slice(self.suffix.len() - suffix..);
+            return futures::future::ready(Ok(bytes)).boxed();
+        }
+        async move {
+            let mut reader = &mut *self.reader;
+            reader.fetch_suffix(suffix).await
+        }
+        .boxed()
+    }
+}
+
+/// The size of the file from the `Content-Range` header of a response, e.g. `bytes 0-0/1234`.
+fn content_range_size(response: &Response) -> Option<u64> {
+    let content_range = response.headers().get(CONTENT_RANGE)?.to_str().ok()?;
+    content_range.rsplit_once('/')?.1.trim().parse().ok()
+}
+
 #[derive(Debug, Clone)]
 struct WrappedFile {
     inner: web_sys::Blob,
@@ -322,77 +1142,176 @@ impl WrappedFile {
         Self { inner, size }
     }
 
-    pub async fn get_bytes(&mut self, range: Range<u64>) -> crate::error::Result<Vec<u8>> {
+    pub async fn get_bytes(
+        &mut self,
+        range: Range<u64>,
+        signal: Option<Signal>,
+    ) -> crate::error::Result<Vec<u8>> {
         use js_sys::Uint8Array;
         use wasm_bindgen_futures::JsFuture;
         let (sender, receiver) = oneshot::channel();
         let file = self.inner.clone();
         spawn_local(async move {
-            if range.start <= utils::MAX_EXACT_INTEGER && range.end <= utils::MAX_EXACT_INTEGER {
-                let subset_blob = file
-                    .slice_with_f64_and_f64(range.start as f64, range.end as f64)
-                    .unwrap();
-                let buf = JsFuture::from(subset_blob.array_buffer()).await.unwrap();
-                let out_vec = Uint8Array::new_with_byte_offset(&buf, 0).to_vec();
-                sender.send(Ok(out_vec)).unwrap();
+            let result = if range.start <= utils::MAX_EXACT_INTEGER
+                && range.end <= utils::MAX_EXACT_INTEGER
+            {
+                let read_slice = async {
+                    let subset_blob =
+                        file.slice_with_f64_and_f64(range.start as f64, range.end as f64)?;
+                    JsFuture::from(subset_blob.array_buffer()).await
+                };
+                // The Blob read itself cannot be cancelled, but its result is discarded
+                match abortable(signal.as_ref(), read_slice).await {
+                    Some(result) => result
+                        .map(|buf| Uint8Array::new_with_byte_offset(&buf, 0).to_vec())
+                        .map_err(|err| {
+                            ParquetWasmError::PlatformSupportError(format!(
+                                "Failed to read {range:?} from Blob: {}",
+                                utils::js_error_message(&err)
+                            ))
+                        }),
+                    None => Err(ParquetWasmError::Aborted),
+                }
             } else {
-                sender
-                    .send(Err(crate::error::ParquetWasmError::PlatformSupportError(
-                        format!("{range:?} is too large to convert into a Blob slice"),
-                    )))
-                    .unwrap();
+                Err(ParquetWasmError::PlatformSupportError(format!(
+                    "{range:?} is too large to convert into a Blob slice"
+                )))
             };
+            let _ = sender.send(result);
         });
 
-        receiver.await.unwrap()
+        receiver.await.map_err(cancelled)?
     }
 }
 
 async fn get_bytes_file(
     mut file: WrappedFile,
     range: Range<u64>,
+    signal: Option<Signal>,
+    progress: Option<Progress>,
 ) -> parquet::errors::Result<Bytes> {
+    report(progress.as_ref(), |progress| {
+        progress.bytes_requested += range.end - range.start
+    });
     let (sender, receiver) = oneshot::channel();
     spawn_local(async move {
         let result = file
-            .get_bytes(range)
+            .get_bytes(range, signal)
             .await
             .map(Bytes::from)
             .map_err(ParquetError::from);
-        sender.send(result).unwrap()
+        let _ = sender.send(result);
     });
-    receiver.await.unwrap()
+    let bytes = receiver
+        .await
+        .map_err(|err| ParquetError::from(cancelled(err)))??;
+    report(progress.as_ref(), |progress| {
+        progress.bytes_received += bytes.len() as u64
+    });
+    Ok(bytes)
 }
 
 #[derive(Debug, Clone)]
 pub struct JsFileReader {
     file: WrappedFile,
-    coalesce_byte_size: u64,
+    io_options: IoOptions,
+    signal: Option<Signal>,
+    progress: Option<Progress>,
 }
 
 impl JsFileReader {
     pub fn new(file: web_sys::Blob, coalesce_byte_size: u64) -> Self {
         Self {
             file: WrappedFile::new(file),
-            coalesce_byte_size,
+            io_options: IoOptions::new(coalesce_byte_size),
+            signal: None,
+            progress: None,
         }
     }
+
+    /// Coalesce, split and parallelize reads according to `io_options`.
+    pub fn with_io_options(self, io_options: IoOptions) -> Self {
+        Self { io_options, ..self }
+    }
+
+    /// Fail reads once `signal` is aborted.
+    pub fn with_signal(self, signal: Option<Signal>) -> Self {
+        Self { signal, ..self }
+    }
+
+    /// Report the bytes requested and received to `progress`.
+    pub fn with_progress(self, progress: Option<Progress>) -> Self {
+        Self { progress, ..self }
+    }
+}
+
+/// A reader of a file in WebAssembly memory.
+#[derive(Debug, Clone)]
+pub struct BufferReader {
+    data: Bytes,
+    progress: Option<Progress>,
+}
+
+impl BufferReader {
+    pub fn new(data: Bytes) -> Self {
+        Self {
+            data,
+            progress: None,
+        }
+    }
+
+    /// Report the bytes read to `progress`.
+    pub fn with_progress(self, progress: Option<Progress>) -> Self {
+        Self { progress, ..self }
+    }
+
+    fn slice(&self, range: Range<u64>) -> parquet::errors::Result<Bytes> {
+        let size = self.data.len() as u64;
+        if range.start > range.end || range.end > size {
+            return Err(ParquetError::EOF(format!(
+                "{range:?} is out of bounds of a file of {size} bytes"
+            )));
+        }
+        let length = range.end - range.start;
+        report(self.progress.as_ref(), |progress| {
+            progress.bytes_requested += length;
+            progress.bytes_received += length;
+        });
+        Ok(self.data.slice(range.start as usize..range.end as usize))
+    }
+}
+
+impl AsyncFileReader for BufferReader {
+    fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
+        futures::future::ready(self.slice(range)).boxed()
+    }
+
+    fn get_byte_ranges(
+        &mut self,
+        ranges: Vec<Range<u64>>,
+    ) -> BoxFuture<'_, parquet::errors::Result<Vec<Bytes>>> {
+        let bytes = ranges.into_iter().map(|range| self.slice(range)).collect();
+        futures::future::ready(bytes).boxed()
+    }
+
+    fn get_metadata<'a>(
+        &'a mut self,
+        _options: Option<&'a ArrowReaderOptions>,
+    ) -> BoxFuture<'a, parquet::errors::Result<Arc<ParquetMetaData>>> {
+        let metadata = ParquetMetaDataReader::new()
+            .with_page_index_policy(PageIndexPolicy::Optional)
+            .parse_and_finish(&self.data)
+            .map(Arc::new);
+        futures::future::ready(metadata).boxed()
+    }
 }
 
 impl AsyncFileReader for JsFileReader {
     fn get_bytes(&mut self, range: Range<u64>) -> BoxFuture<'_, parquet::errors::Result<Bytes>> {
         async move {
-            let (sender, receiver) = oneshot::channel();
-            let mut file = self.file.clone();
-            spawn_local(async move {
-                let result = file
-                    .get_bytes(range)
-                    .await
-                    .map(Bytes::from)
-                    .map_err(ParquetError::from);
-                sender.send(result).unwrap()
-            });
-            receiver.await.unwrap()
+            // Split the range if it exceeds the maximum request size
+            let mut bytes = self.get_byte_ranges(vec![range]).await?;
+            Ok(bytes.pop().unwrap_or_default())
         }
         .boxed()
     }
@@ -404,8 +1323,15 @@ impl AsyncFileReader for JsFileReader {
         async move {
             coalesce_ranges(
                 &ranges,
-                |range| get_bytes_file(self.file.clone(), range),
-                self.coalesce_byte_size,
+                |range| {
+                    get_bytes_file(
+                        self.file.clone(),
+                        range,
+                        self.signal.clone(),
+                        self.progress.clone(),
+                    )
+                },
+                &self.io_options,
             )
             .await
         }
@@ -428,28 +1354,6 @@ impl AsyncFileReader for JsFileReader {
     }
 }
 
-pub async fn make_range_request_with_client(
-    url: String,
-    client: Client,
-    range_str: String,
-) -> std::result::Result<Bytes, JsValue> {
-    let (sender, receiver) = oneshot::channel();
-    spawn_local(async move {
-        let resp = client
-            .get(url)
-            .header("Range", range_str)
-            .send()
-            .await
-            .unwrap()
-            .error_for_status()
-            .unwrap();
-        let bytes = resp.bytes().await.unwrap();
-        sender.send(bytes).unwrap();
-    });
-    let data = receiver.await.unwrap();
-    Ok(data)
-}
-
 pub async fn read_metadata_async(
     url: String,
     content_length: Option<usize>,
@@ -480,7 +1384,7 @@ pub async fn _read_row_group(
 
     let mut compat = reader.compat();
     let metadata = ArrowReaderMetadata::load_async(&mut compat, Default::default()).await?;
-    let builder = create_builder(compat, &metadata, &Default::default())?;
+    let (builder, _) = create_builder(compat, &metadata, &Default::default())?;
 
     let arrow_schema = builder.schema().clone();
     let parquet_reader = builder.with_row_groups(vec![row_group]).build()?;
@@ -518,7 +1422,7 @@ pub async fn read_record_batch_stream(
 
     let mut compat = reader.compat();
     let metadata = ArrowReaderMetadata::load_async(&mut compat, Default::default()).await?;
-    let builder = create_builder(compat, &metadata, &Default::default())?;
+    let (builder, _) = create_builder(compat, &metadata, &Default::default())?;
     let parquet_reader = builder.build()?;
     Ok(parquet_reader)
 }
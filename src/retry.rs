@@ -0,0 +1,160 @@
+//! Retry range requests that fail with transient errors.
+//!
+//! Requests are retried on network errors, on `408`, `429` and `5xx` responses. The delay before
+//! each retry grows exponentially with "full jitter", i.e. a random delay up to the exponential
+//! backoff, unless the response has a `Retry-After` header.
+
+use std::future::Future;
+
+use js_sys::Promise;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::{ParquetWasmError, Result};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_RetryOptions: &'static str = r#"
+export type RetryOptions = {
+    /* The maximum number of attempts of each request, including the first one. Defaults to 3. Set to 1 to disable retries. */
+    maxAttempts?: number;
+    /* The upper bound of the random delay in milliseconds before the first retry, doubled for every following retry. Defaults to 250. */
+    initialDelay?: number;
+    /* The maximum delay in milliseconds before a retry, including delays requested by a `Retry-After` header. Defaults to 10000. */
+    maxDelay?: number;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: f64) -> JsValue;
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryOptions {
+    /// The maximum number of attempts of each request, including the first one.
+    pub max_attempts: u32,
+
+    /// The upper bound of the delay in milliseconds before the first retry.
+    pub initial_delay: f64,
+
+    /// The maximum delay in milliseconds before a retry.
+    pub max_delay: f64,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: 250.0,
+            max_delay: 10_000.0,
+        }
+    }
+}
+
+impl RetryOptions {
+    /// The delay in milliseconds before retrying a request that failed `attempts` times.
+    fn delay(&self, attempts: u32, retry_after: Option<f64>) -> f64 {
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => {
+                let backoff = self.initial_delay * 2f64.powi(attempts.saturating_sub(1) as i32);
+                js_sys::Math::random() * backoff.min(self.max_delay)
+            }
+        };
+        delay.clamp(0.0, self.max_delay)
+    }
+}
+
+/// A failed attempt of a request.
+pub(crate) struct AttemptError {
+    error: ParquetWasmError,
+    retryable: bool,
+    /// The delay in milliseconds requested by the server
+    retry_after: Option<f64>,
+}
+
+impl AttemptError {
+    /// An error that retrying would not fix.
+    pub(crate) fn permanent(error: ParquetWasmError) -> Self {
+        Self {
+            error,
+            retryable: false,
+            retry_after: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AttemptError {
+    fn from(error: reqwest::Error) -> Self {
+        // In the browser, a failed `fetch` is a request error and a connection dropped while
+        // reading the body is a decode error
+        let retryable =
+            error.is_request() || error.is_timeout() || error.is_body() || error.is_decode();
+        Self {
+            error: error.into(),
+            retryable,
+            retry_after: None,
+        }
+    }
+}
+
+/// Turn an error response into an error, which is retryable if the status is transient.
+pub(crate) fn error_for_status(response: Response) -> std::result::Result<Response, AttemptError> {
+    let status = response.status();
+    let retryable = status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT;
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after);
+    response.error_for_status().map_err(|error| AttemptError {
+        error: error.into(),
+        retryable,
+        retry_after,
+    })
+}
+
+/// Parse a `Retry-After` header, either a number of seconds or an HTTP date, into a delay in
+/// milliseconds.
+fn parse_retry_after(value: &str) -> Option<f64> {
+    if let Ok(seconds) = value.trim().parse::<f64>() {
+        return Some(seconds * 1000.0);
+    }
+    let date = js_sys::Date::parse(value);
+    (!date.is_nan()).then(|| date - js_sys::Date::now())
+}
+
+/// Make a request, retrying it while it fails with retryable errors.
+///
+/// This must be called from a local task, as it waits for JS timers.
+pub(crate) async fn with_retries<T, F, Fut>(options: &RetryOptions, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, AttemptError>>,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match attempt().await {
+            Ok(output) => return Ok(output),
+            Err(err) if err.retryable && attempts < options.max_attempts => {
+                sleep(options.delay(attempts, err.retry_after)).await;
+            }
+            Err(err) => return Err(err.error),
+        }
+    }
+}
+
+async fn sleep(milliseconds: f64) {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        set_timeout(&resolve, milliseconds);
+    });
+    let _ = JsFuture::from(promise).await;
+}
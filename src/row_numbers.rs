@@ -0,0 +1,202 @@
+//! Track the position in the file of each row returned by a reader.
+//!
+//! The rows that a reader returns are determined by the selected row groups, the row selection
+//! derived from the page index, the row filter, and finally the offset and limit. The first two
+//! are known before reading. The rows passing the row filter are recorded while its predicates
+//! are evaluated, which happens before the matching rows of a row group are decoded.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use arrow::array::{RecordBatch, RecordBatchOptions, UInt64Array};
+use arrow::error::ArrowError;
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::arrow_reader::{ArrowPredicate, ArrowPredicateFn, RowFilter, RowSelection};
+use parquet::errors::ParquetError;
+use parquet::file::metadata::ParquetMetaData;
+use parquet::schema::types::SchemaDescriptor;
+
+use crate::error::Result;
+use crate::filter::FilterExpression;
+use crate::read_options::generate_projection_mask;
+
+/// The data type of row number columns
+pub const ROW_NUMBER_TYPE: DataType = DataType::UInt64;
+
+/// A queue of row numbers, stored as ranges of consecutive rows.
+#[derive(Debug, Default)]
+struct RowQueue(VecDeque<Range<u64>>);
+
+impl RowQueue {
+    fn push(&mut self, range: Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        if let Some(last) = self.0.back_mut() {
+            if last.end == range.start {
+                last.end = range.end;
+                return;
+            }
+        }
+        self.0.push_back(range);
+    }
+
+    /// Remove the next `n` rows from the queue.
+    fn pop(&mut self, mut n: usize) -> Vec<Range<u64>> {
+        let mut ranges = vec![];
+        while n > 0 {
+            let Some(front) = self.0.front_mut() else {
+                break;
+            };
+            let len = (front.end - front.start).min(n as u64);
+            ranges.push(front.start..front.start + len);
+            front.start += len;
+            if front.is_empty() {
+                self.0.pop_front();
+            }
+            n -= len as usize;
+        }
+        ranges
+    }
+}
+
+/// The row numbers of the rows returned by a reader, in order.
+pub struct RowNumbers {
+    /// The name of the row number column
+    name: String,
+    /// The number of rows still to be skipped because of the reader's offset
+    offset: usize,
+    rows: Arc<Mutex<RowQueue>>,
+}
+
+impl RowNumbers {
+    /// Track the rows of `row_groups` that are selected by `selection`, then by `filter` and
+    /// finally by `offset`.
+    ///
+    /// If a filter is provided, the returned [`RowFilter`] must be used in place of the one
+    /// compiled from the filter, so that the rows passing it are recorded.
+    pub fn try_new(
+        name: &str,
+        metadata: &ParquetMetaData,
+        parquet_schema: &SchemaDescriptor,
+        row_groups: &[usize],
+        selection: Option<&RowSelection>,
+        filter: Option<&FilterExpression>,
+        offset: Option<usize>,
+    ) -> Result<(Self, Option<RowFilter>)> {
+        let mut row_group_start = vec![0; metadata.num_row_groups()];
+        let mut start = 0;
+        for (i, row_group) in metadata.row_groups().iter().enumerate() {
+            row_group_start[i] = start;
+            start += row_group.num_rows() as u64;
+        }
+
+        let mut all_rows = RowQueue::default();
+        for row_group in row_groups {
+            let (Some(start), Some(row_group_metadata)) = (
+                row_group_start.get(*row_group),
+                metadata.row_groups().get(*row_group),
+            ) else {
+                return Err(ParquetError::General(format!(
+                    "Row group {row_group} out of bounds for file with {} row groups",
+                    metadata.num_row_groups()
+                ))
+                .into());
+            };
+            all_rows.push(*start..*start + row_group_metadata.num_rows() as u64);
+        }
+        let selected_rows = match selection {
+            Some(selection) => {
+                let mut selected_rows = RowQueue::default();
+                for selector in selection.iter() {
+                    let ranges = all_rows.pop(selector.row_count);
+                    if !selector.skip {
+                        ranges
+                            .into_iter()
+                            .for_each(|range| selected_rows.push(range));
+                    }
+                }
+                selected_rows
+            }
+            None => all_rows,
+        };
+
+        let mut rows = Arc::new(Mutex::new(selected_rows));
+        let row_filter = match filter {
+            Some(filter) => {
+                // Each predicate passes the rows that match it on to the next one
+                let mut predicates = vec![];
+                for expr in filter.conjuncts() {
+                    let projection = generate_projection_mask(&expr.columns(), parquet_schema)?;
+                    let expr = expr.clone();
+                    let input = rows;
+                    let output = Arc::new(Mutex::new(RowQueue::default()));
+                    rows = output.clone();
+                    let predicate = ArrowPredicateFn::new(projection, move |batch| {
+                        let result = expr.evaluate(&batch)?;
+                        let input_rows = input.lock().unwrap().pop(batch.num_rows());
+                        let mut output = output.lock().unwrap();
+                        let mut matches = result.iter();
+                        for row in input_rows.into_iter().flatten() {
+                            if matches.next().flatten().unwrap_or(false) {
+                                output.push(row..row + 1);
+                            }
+                        }
+                        Ok(result)
+                    });
+                    predicates.push(Box::new(predicate) as Box<dyn ArrowPredicate>);
+                }
+                Some(RowFilter::new(predicates))
+            }
+            None => None,
+        };
+
+        let row_numbers = Self {
+            name: name.to_string(),
+            offset: offset.unwrap_or(0),
+            rows,
+        };
+        Ok((row_numbers, row_filter))
+    }
+
+    /// Append a column with the row numbers of the next batch returned by the reader.
+    pub fn append(&mut self, batch: RecordBatch) -> std::result::Result<RecordBatch, ArrowError> {
+        let mut rows = self.rows.lock().unwrap();
+        if self.offset > 0 {
+            rows.pop(self.offset);
+            self.offset = 0;
+        }
+        let row_numbers = rows.pop(batch.num_rows()).into_iter().flatten();
+        let row_numbers = UInt64Array::from_iter_values(row_numbers);
+        if row_numbers.len() != batch.num_rows() {
+            return Err(ArrowError::ComputeError(
+                "Row numbers are missing for some rows".to_string(),
+            ));
+        }
+
+        let fields = batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .cloned()
+            .chain([Arc::new(Field::new(&self.name, ROW_NUMBER_TYPE, false))])
+            .collect::<Vec<_>>();
+        let schema = Schema::new_with_metadata(fields, batch.schema_ref().metadata().clone());
+        let mut columns = batch.columns().to_vec();
+        columns.push(Arc::new(row_numbers));
+        let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+        RecordBatch::try_new_with_options(Arc::new(schema), columns, &options)
+    }
+}
+
+/// Append row numbers to a decoded batch, if they were requested.
+pub(crate) fn with_row_numbers(
+    batch: RecordBatch,
+    row_numbers: Option<&mut RowNumbers>,
+) -> std::result::Result<RecordBatch, ArrowError> {
+    match row_numbers {
+        Some(row_numbers) => row_numbers.append(batch),
+        None => Ok(batch),
+    }
+}
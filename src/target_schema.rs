@@ -0,0 +1,196 @@
+//! Read Parquet data as a caller-supplied Arrow schema.
+//!
+//! Where the Parquet decoder supports it (e.g. reading strings as dictionaries or choosing a
+//! timestamp unit), the target types are passed to [`ArrowReaderOptions::with_schema`] so that
+//! data is decoded into them directly. Any other target types are cast to after decoding.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::ipc::reader::StreamReader;
+use arrow_schema::{DataType, Field, FieldRef, Fields, Schema, SchemaRef};
+use js_sys::Uint8Array;
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::error::Result;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_SchemaDescription: &'static str = r#"
+export type FieldDescription = {
+    name: string;
+    /* An Arrow data type in its Rust display format, e.g. "Int64", "Utf8", "Dictionary(Int32, Utf8)" or "Timestamp(Millisecond, Some(\"UTC\"))". */
+    type: string;
+    /* Defaults to true. */
+    nullable?: boolean;
+    metadata?: Record<string, string>;
+};
+
+export type SchemaDescription = {
+    fields: FieldDescription[];
+    metadata?: Record<string, string>;
+};
+"#;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FieldDescription {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    #[serde(default = "default_nullable")]
+    nullable: bool,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SchemaDescription {
+    fields: Vec<FieldDescription>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl TryFrom<SchemaDescription> for Schema {
+    type Error = arrow::error::ArrowError;
+
+    fn try_from(value: SchemaDescription) -> std::result::Result<Self, Self::Error> {
+        let fields = value
+            .fields
+            .into_iter()
+            .map(|field| {
+                let data_type = DataType::from_str(&field.data_type)?;
+                Ok(Field::new(field.name, data_type, field.nullable).with_metadata(field.metadata))
+            })
+            .collect::<std::result::Result<Vec<_>, Self::Error>>()?;
+        Ok(Schema::new_with_metadata(fields, value.metadata))
+    }
+}
+
+/// Parse a target schema passed from JS, either as a `Uint8Array` with an Arrow IPC stream or as
+/// a `SchemaDescription`.
+pub(crate) fn parse_schema(
+    value: JsValue,
+) -> std::result::Result<Option<SchemaRef>, serde_wasm_bindgen::Error> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(None);
+    }
+    let schema = if let Some(array) = value.dyn_ref::<Uint8Array>() {
+        let reader = StreamReader::try_new(Cursor::new(array.to_vec()), None)
+            .map_err(serde_wasm_bindgen::Error::new)?;
+        reader.schema()
+    } else {
+        let description: SchemaDescription = serde_wasm_bindgen::from_value(value)?;
+        Arc::new(Schema::try_from(description).map_err(serde_wasm_bindgen::Error::new)?)
+    };
+    Ok(Some(schema))
+}
+
+/// Decode the columns of the file that are in the target schema as their target types, as far as
+/// the Parquet decoder supports it.
+///
+/// Columns are matched by name. Columns that are not in the target schema keep their types.
+pub(crate) fn apply_to_metadata(
+    metadata: &ArrowReaderMetadata,
+    target: &SchemaRef,
+) -> Result<ArrowReaderMetadata> {
+    let schema = metadata.schema();
+    let try_fields = |fields: Vec<FieldRef>| {
+        let hinted_schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        let arrow_options = ArrowReaderOptions::default().with_schema(Arc::new(hinted_schema));
+        ArrowReaderMetadata::try_new(metadata.metadata().clone(), arrow_options)
+    };
+
+    // Only the type is taken from the target field, as the decoder requires the nullability
+    // and metadata to match the file. These are applied by `output_schema` instead.
+    let hinted_fields = schema
+        .fields()
+        .iter()
+        .map(|field| match target.field_with_name(field.name()) {
+            Ok(target_field) if target_field.data_type() != field.data_type() => Arc::new(
+                field
+                    .as_ref()
+                    .clone()
+                    .with_data_type(target_field.data_type().clone()),
+            ),
+            _ => field.clone(),
+        })
+        .collect::<Vec<_>>();
+    if hinted_fields[..] == schema.fields()[..] {
+        return Ok(metadata.clone());
+    }
+    if let Ok(metadata) = try_fields(hinted_fields.clone()) {
+        return Ok(metadata);
+    }
+
+    // Some target types cannot be decoded into directly, so find the ones that can one column at
+    // a time
+    let mut fields = schema.fields().to_vec();
+    for (i, hinted_field) in hinted_fields.into_iter().enumerate() {
+        if hinted_field == fields[i] {
+            continue;
+        }
+        let mut candidate = fields.clone();
+        candidate[i] = hinted_field;
+        if try_fields(candidate.clone()).is_ok() {
+            fields = candidate;
+        }
+    }
+    Ok(try_fields(fields)?)
+}
+
+/// The schema of the batches returned to the user, given the schema of the decoded batches.
+///
+/// The metadata of the schema is only replaced if the target schema has any.
+pub(crate) fn output_schema(schema: &SchemaRef, target: &SchemaRef) -> SchemaRef {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| match target.field_with_name(field.name()) {
+            Ok(target_field) => project_field(target_field, field),
+            Err(_) => field.clone(),
+        })
+        .collect::<Fields>();
+    let metadata = if target.metadata().is_empty() {
+        schema.metadata().clone()
+    } else {
+        target.metadata().clone()
+    };
+    Arc::new(Schema::new_with_metadata(fields, metadata))
+}
+
+/// The target field, with only the children of nested types that were decoded, e.g. when only
+/// some children of a struct are read.
+fn project_field(target: &Field, decoded: &Field) -> FieldRef {
+    let project_fields = |target_fields: &Fields, decoded_fields: &Fields| {
+        decoded_fields
+            .iter()
+            .map(|field| match target_fields.find(field.name()) {
+                Some((_, target_field)) => project_field(target_field, field),
+                None => field.clone(),
+            })
+            .collect::<Fields>()
+    };
+    let data_type = match (target.data_type(), decoded.data_type()) {
+        (DataType::Struct(target_fields), DataType::Struct(decoded_fields)) => {
+            DataType::Struct(project_fields(target_fields, decoded_fields))
+        }
+        (DataType::List(target_field), DataType::List(decoded_field)) => {
+            DataType::List(project_field(target_field, decoded_field))
+        }
+        (DataType::LargeList(target_field), DataType::LargeList(decoded_field)) => {
+            DataType::LargeList(project_field(target_field, decoded_field))
+        }
+        (DataType::Map(target_field, sorted), DataType::Map(decoded_field, _)) => {
+            DataType::Map(project_field(target_field, decoded_field), *sorted)
+        }
+        (data_type, _) => data_type.clone(),
+    };
+    Arc::new(target.clone().with_data_type(data_type))
+}
@@ -0,0 +1,224 @@
+//! Coerce the Arrow types read from Parquet into types that are easier to consume from JS.
+//!
+//! Coercions that the Parquet decoder supports natively (e.g. reading binary data as strings or
+//! choosing 32-bit offsets) are applied through the Arrow schema passed to
+//! [`ArrowReaderOptions::with_schema`]. The remaining ones (e.g. casting decimals) are applied to
+//! each decoded [`RecordBatch`].
+
+use std::sync::Arc;
+
+use arrow::array::{RecordBatch, RecordBatchOptions};
+use arrow::compute::cast;
+use arrow::error::ArrowError;
+use arrow_schema::{DataType, FieldRef, Fields, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::error::Result;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TypeCoercion: &'static str = r#"
+export type TypeCoercion = {
+    /* Read 64-bit integer columns as BigInt (the default) or cast them to Float64. Values beyond 2^53 lose precision as Float64. */
+    int64?: "bigint" | "float64";
+    /* Read decimal columns as Decimal (the default), or cast them to Float64 or to strings. */
+    decimal?: "decimal" | "float64" | "string";
+    /* Cast all timestamp columns to this unit. Casting to a coarser unit truncates values. */
+    timestampUnit?: "s" | "ms" | "us" | "ns";
+    /* Read LargeUtf8, LargeBinary and LargeList columns as Utf8, Binary and List. */
+    downcastLargeTypes?: boolean;
+    /* Read binary columns without a string annotation as Utf8. Reading fails if the data is not valid UTF-8. */
+    binaryAsString?: boolean;
+};
+"#;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Int64Coercion {
+    #[default]
+    BigInt,
+    Float64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecimalCoercion {
+    #[default]
+    Decimal,
+    Float64,
+    String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimestampUnit {
+    #[serde(rename = "s")]
+    Second,
+    #[serde(rename = "ms")]
+    Millisecond,
+    #[serde(rename = "us")]
+    Microsecond,
+    #[serde(rename = "ns")]
+    Nanosecond,
+}
+
+impl From<TimestampUnit> for TimeUnit {
+    fn from(value: TimestampUnit) -> Self {
+        match value {
+            TimestampUnit::Second => TimeUnit::Second,
+            TimestampUnit::Millisecond => TimeUnit::Millisecond,
+            TimestampUnit::Microsecond => TimeUnit::Microsecond,
+            TimestampUnit::Nanosecond => TimeUnit::Nanosecond,
+        }
+    }
+}
+
+/// A policy for converting the Arrow types read from Parquet.
+///
+/// The default policy leaves all types unchanged.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeCoercion {
+    #[serde(default)]
+    pub int64: Int64Coercion,
+    #[serde(default)]
+    pub decimal: DecimalCoercion,
+    pub timestamp_unit: Option<TimestampUnit>,
+    #[serde(default)]
+    pub downcast_large_types: bool,
+    #[serde(default)]
+    pub binary_as_string: bool,
+}
+
+impl TypeCoercion {
+    /// Apply the coercions that are supported by the Parquet decoder to the reader metadata.
+    pub(crate) fn apply_to_metadata(
+        &self,
+        metadata: &ArrowReaderMetadata,
+    ) -> Result<ArrowReaderMetadata> {
+        let schema = metadata.schema();
+        let fields = self.decoded_fields(schema.fields());
+        if &fields == schema.fields() {
+            return Ok(metadata.clone());
+        }
+
+        let new_schema = Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()));
+        let arrow_options = ArrowReaderOptions::default().with_schema(new_schema);
+        Ok(ArrowReaderMetadata::try_new(
+            metadata.metadata().clone(),
+            arrow_options,
+        )?)
+    }
+
+    /// The schema of the batches returned to the user, given the schema of the decoded batches.
+    pub(crate) fn output_schema(&self, schema: &SchemaRef) -> SchemaRef {
+        let fields = self.output_fields(schema.fields());
+        if &fields == schema.fields() {
+            return schema.clone();
+        }
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    }
+
+    fn decoded_fields(&self, fields: &Fields) -> Fields {
+        map_fields(fields, &|data_type| match data_type {
+            DataType::LargeUtf8 if self.downcast_large_types => Some(DataType::Utf8),
+            DataType::LargeBinary if self.downcast_large_types && self.binary_as_string => {
+                Some(DataType::Utf8)
+            }
+            DataType::LargeBinary if self.downcast_large_types => Some(DataType::Binary),
+            DataType::LargeBinary if self.binary_as_string => Some(DataType::LargeUtf8),
+            DataType::Binary if self.binary_as_string => Some(DataType::Utf8),
+            DataType::LargeList(field) if self.downcast_large_types => {
+                Some(DataType::List(field.clone()))
+            }
+            _ => None,
+        })
+    }
+
+    fn output_fields(&self, fields: &Fields) -> Fields {
+        map_fields(fields, &|data_type| match data_type {
+            DataType::Int64 | DataType::UInt64 if self.int64 == Int64Coercion::Float64 => {
+                Some(DataType::Float64)
+            }
+            DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => match self.decimal {
+                DecimalCoercion::Decimal => None,
+                DecimalCoercion::Float64 => Some(DataType::Float64),
+                DecimalCoercion::String => Some(DataType::Utf8),
+            },
+            DataType::Timestamp(unit, tz) => match self.timestamp_unit {
+                Some(new_unit) if TimeUnit::from(new_unit) != *unit => {
+                    Some(DataType::Timestamp(new_unit.into(), tz.clone()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+/// Recursively replace data types in the fields, including the children of nested types.
+///
+/// `replace` returns the new type of a data type, or `None` to keep it.
+fn map_fields(fields: &Fields, replace: &dyn Fn(&DataType) -> Option<DataType>) -> Fields {
+    fields
+        .iter()
+        .map(|field| map_field(field, replace))
+        .collect()
+}
+
+fn map_field(field: &FieldRef, replace: &dyn Fn(&DataType) -> Option<DataType>) -> FieldRef {
+    let new_data_type = map_data_type(field.data_type(), replace);
+    if &new_data_type == field.data_type() {
+        field.clone()
+    } else {
+        Arc::new(field.as_ref().clone().with_data_type(new_data_type))
+    }
+}
+
+fn map_data_type(
+    data_type: &DataType,
+    replace: &dyn Fn(&DataType) -> Option<DataType>,
+) -> DataType {
+    match data_type {
+        DataType::Struct(struct_fields) => DataType::Struct(map_fields(struct_fields, replace)),
+        DataType::List(inner_field) => DataType::List(map_field(inner_field, replace)),
+        DataType::FixedSizeList(inner_field, list_size) => {
+            DataType::FixedSizeList(map_field(inner_field, replace), *list_size)
+        }
+        DataType::LargeList(inner_field) => {
+            // Large lists may themselves be replaced, e.g. with 32-bit lists
+            let data_type = DataType::LargeList(map_field(inner_field, replace));
+            replace(&data_type).unwrap_or(data_type)
+        }
+        DataType::Map(entries_field, sorted) => {
+            DataType::Map(map_field(entries_field, replace), *sorted)
+        }
+        // Only the values of a dictionary are replaced, its keys stay integers
+        DataType::Dictionary(key_type, value_type) => DataType::Dictionary(
+            key_type.clone(),
+            Box::new(map_data_type(value_type, replace)),
+        ),
+        other => replace(other).unwrap_or_else(|| other.clone()),
+    }
+}
+
+/// Cast a decoded batch to the output schema returned by [`JsReaderOptions::output_schema`].
+///
+/// [`JsReaderOptions::output_schema`]: crate::read_options::JsReaderOptions::output_schema
+pub(crate) fn cast_batch(
+    batch: RecordBatch,
+    schema: &SchemaRef,
+) -> std::result::Result<RecordBatch, ArrowError> {
+    if batch.schema_ref() == schema {
+        return Ok(batch);
+    }
+
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields())
+        .map(|(column, field)| cast(column, field.data_type()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    RecordBatch::try_new_with_options(schema.clone(), columns, &options)
+}
@@ -40,3 +40,20 @@ pub fn assert_parquet_file_not_empty(parquet_file: &[u8]) -> Result<(), JsError>
     }
     Ok(())
 }
+
+/// The message of a value thrown or rejected by JS, for use in a Rust error.
+pub fn js_error_message(value: &JsValue) -> String {
+    if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+        return error.message().into();
+    }
+    value.as_string().unwrap_or_else(|| format!("{value:?}"))
+}
+
+/// The bytes of a `Uint8Array` or `ArrayBuffer` returned by JS.
+pub fn js_bytes(value: &JsValue) -> Option<Vec<u8>> {
+    if let Some(array) = value.dyn_ref::<js_sys::Uint8Array>() {
+        return Some(array.to_vec());
+    }
+    let buffer = value.dyn_ref::<js_sys::ArrayBuffer>()?;
+    Some(js_sys::Uint8Array::new(buffer).to_vec())
+}
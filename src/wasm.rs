@@ -1,7 +1,8 @@
+#[cfg(feature = "reader")]
+use crate::buffer::{ParquetBuffer, ParquetInput};
 use crate::error::WasmResult;
 #[cfg(feature = "reader")]
 use crate::read_options::ReaderOptions;
-use crate::utils::assert_parquet_file_not_empty;
 use arrow_wasm::{RecordBatch, Schema, Table};
 use wasm_bindgen::prelude::*;
 
@@ -52,7 +53,7 @@ use wasm_bindgen::prelude::*;
 /// );
 /// ```
 ///
-/// @param parquet_file Uint8Array containing Parquet data
+/// @param parquet_file Uint8Array or ArrayBuffer containing Parquet data
 /// @param options
 ///
 ///    Options for reading Parquet data. Optional keys include:
@@ -63,12 +64,43 @@ use wasm_bindgen::prelude::*;
 ///    - `limit`: Provide a limit to the number of rows to be read.
 ///    - `offset`: Provide an offset to skip over the given number of rows.
 ///    - `columns`: The column names from the file to read.
+///    - `filter`: Only read rows matching this {@linkcode FilterExpression}.
+///    - `typeCoercion`: Convert the Arrow types read from the file. See {@linkcode TypeCoercion}.
+///    - `schema`: Read columns as the types of the fields with the same name in this schema,
+///           given as an Arrow IPC stream or a {@linkcode SchemaDescription}.
+///    - `skipArrowMetadata`: Ignore the Arrow schema stored in the file by the writer.
+///    - `rowNumberColumn`: Append a column with this name holding the 0-based position of each
+///           row in the file.
 #[wasm_bindgen(js_name = readParquet)]
 #[cfg(feature = "reader")]
-pub fn read_parquet(parquet_file: Vec<u8>, options: Option<ReaderOptions>) -> WasmResult<Table> {
-    assert_parquet_file_not_empty(parquet_file.as_slice())?;
+pub fn read_parquet(
+    parquet_file: ParquetInput,
+    options: Option<ReaderOptions>,
+) -> WasmResult<Table> {
     Ok(crate::reader::read_parquet(
-        parquet_file,
+        parquet_file.to_bytes()?,
+        options
+            .map(|x| x.try_into())
+            .transpose()?
+            .unwrap_or_default(),
+    )?)
+}
+
+/// Read a Parquet file in a {@linkcode ParquetBuffer} into Arrow data, without copying it.
+///
+/// The buffer can no longer be written to once it has been read.
+///
+/// @param buffer ParquetBuffer containing Parquet data
+/// @param options Options for reading Parquet data. See {@linkcode readParquet} for the
+///     supported keys.
+#[wasm_bindgen(js_name = readParquetBuffer)]
+#[cfg(feature = "reader")]
+pub fn read_parquet_buffer(
+    buffer: &ParquetBuffer,
+    options: Option<ReaderOptions>,
+) -> WasmResult<Table> {
+    Ok(crate::reader::read_parquet(
+        buffer.to_bytes()?,
         options
             .map(|x| x.try_into())
             .transpose()?
@@ -122,12 +154,42 @@ pub fn read_parquet(parquet_file: Vec<u8>, options: Option<ReaderOptions>) -> Wa
 /// const arrowSchema = arrowTable.schema;
 /// ```
 ///
-/// @param parquet_file Uint8Array containing Parquet data
+/// @param parquet_file Uint8Array or ArrayBuffer containing Parquet data
 #[wasm_bindgen(js_name = readSchema)]
 #[cfg(feature = "reader")]
-pub fn read_schema(parquet_file: Vec<u8>) -> WasmResult<Schema> {
-    assert_parquet_file_not_empty(parquet_file.as_slice())?;
-    Ok(crate::reader::read_schema(parquet_file)?)
+pub fn read_schema(parquet_file: ParquetInput) -> WasmResult<Schema> {
+    Ok(crate::reader::read_schema(parquet_file.to_bytes()?)?)
+}
+
+/// Read an Arrow schema from a Parquet file in a {@linkcode ParquetBuffer}.
+///
+/// @param buffer ParquetBuffer containing Parquet data
+#[wasm_bindgen(js_name = readSchemaBuffer)]
+#[cfg(feature = "reader")]
+pub fn read_schema_buffer(buffer: &ParquetBuffer) -> WasmResult<Schema> {
+    Ok(crate::reader::read_schema(buffer.to_bytes()?)?)
+}
+
+/// Read the metadata of a Parquet file in memory.
+///
+/// This includes the page index of the file, if present.
+///
+/// @param parquet_file Uint8Array or ArrayBuffer containing Parquet data
+#[wasm_bindgen(js_name = readMetadata)]
+#[cfg(feature = "reader")]
+pub fn read_metadata(parquet_file: ParquetInput) -> WasmResult<crate::metadata::ParquetMetaData> {
+    Ok(crate::reader::read_metadata(parquet_file.to_bytes()?)?.into())
+}
+
+/// Read the metadata of a Parquet file in a {@linkcode ParquetBuffer}.
+///
+/// @param buffer ParquetBuffer containing Parquet data
+#[wasm_bindgen(js_name = readMetadataBuffer)]
+#[cfg(feature = "reader")]
+pub fn read_metadata_buffer(
+    buffer: &ParquetBuffer,
+) -> WasmResult<crate::metadata::ParquetMetaData> {
+    Ok(crate::reader::read_metadata(buffer.to_bytes()?)?.into())
 }
 
 /// Write Arrow data to a Parquet file.
@@ -244,9 +306,9 @@ pub async fn read_parquet_stream(
 ) -> WasmResult<wasm_streams::readable::sys::ReadableStream> {
     use futures::StreamExt;
     let parquet_stream = crate::reader_async::read_record_batch_stream(url, content_length).await?;
-    let stream = parquet_stream.map(|maybe_record_batch| {
-        let record_batch = maybe_record_batch.unwrap();
-        Ok(RecordBatch::new(record_batch).into())
+    let stream = parquet_stream.map(|maybe_record_batch| match maybe_record_batch {
+        Ok(record_batch) => Ok(RecordBatch::new(record_batch).into()),
+        Err(err) => Err(JsError::from(err).into()),
     });
     Ok(wasm_streams::ReadableStream::from_stream(stream).into_raw())
 }
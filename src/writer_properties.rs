@@ -170,6 +170,14 @@ impl WriterPropertiesBuilder {
         Self(self.0.set_statistics_enabled(value.into()))
     }
 
+    /// Sets flag to enable/disable bloom filters for any column.
+    ///
+    /// Bloom filters allow readers to skip row groups that cannot contain a given value.
+    #[wasm_bindgen(js_name = setBloomFilterEnabled)]
+    pub fn set_bloom_filter_enabled(self, value: bool) -> Self {
+        Self(self.0.set_bloom_filter_enabled(value))
+    }
+
     // ----------------------------------------------------------------------
     // Setters for a specific column
 
@@ -215,6 +223,14 @@ impl WriterPropertiesBuilder {
                 .set_column_statistics_enabled(column_path, value.into()),
         )
     }
+
+    /// Sets flag to enable/disable bloom filters for a column.
+    /// Takes precedence over globally defined settings.
+    #[wasm_bindgen(js_name = setColumnBloomFilterEnabled)]
+    pub fn set_column_bloom_filter_enabled(self, col: String, value: bool) -> Self {
+        let column_path = parquet::schema::types::ColumnPath::from(col);
+        Self(self.0.set_column_bloom_filter_enabled(column_path, value))
+    }
 }
 
 impl Default for WriterPropertiesBuilder {
@@ -0,0 +1,70 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import { temporaryServer } from "./utils";
+
+describe("cancel async reads with an AbortSignal", async (t) => {
+  // Four row groups of five rows each
+  const table = arrow.tableFromArrays({
+    value: Int32Array.from({ length: 20 }, (_, i) => i),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(5)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  it("rejects fromUrl", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/1-partition-none.parquet`;
+
+    const controller = new AbortController();
+    const opening = wasm.ParquetFile.fromUrl(url, {
+      signal: controller.signal,
+    });
+    controller.abort();
+    await expect(opening).rejects.toThrowError("The operation was aborted");
+
+    // The signal does not apply to reads after the file was opened
+    const opened = await wasm.ParquetFile.fromUrl(url, {
+      signal: new AbortController().signal,
+    });
+    const read = arrow.tableFromIPC((await opened.read()).intoIPCStream());
+    expect(read.numRows).toBeGreaterThan(0);
+    await server.close();
+  });
+
+  it("rejects read", async () => {
+    const controller = new AbortController();
+    controller.abort();
+    await expect(
+      file.read({ signal: controller.signal })
+    ).rejects.toThrowError("The operation was aborted");
+  });
+
+  it("errors the stream", async () => {
+    const controller = new AbortController();
+    const stream = (await file.stream({
+      signal: controller.signal,
+    })) as ReadableStream<wasm.RecordBatch>;
+    let numBatches = 0;
+    const readAll = async () => {
+      for await (const batch of stream) {
+        batch.free();
+        numBatches += 1;
+        controller.abort();
+      }
+    };
+    await expect(readAll()).rejects.toThrowError("The operation was aborted");
+    expect(numBatches).toBeLessThan(4);
+  });
+
+  it("leaves the file usable", async () => {
+    const read = arrow.tableFromIPC((await file.read()).intoIPCStream());
+    expect(read.numRows).toStrictEqual(20);
+  });
+});
@@ -0,0 +1,101 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+describe("bloom filter", async (t) => {
+  // Four row groups of 100 ids each, with bloom filters on the id and name columns
+  const ids = Int32Array.from({ length: 400 }, (_, i) => i * 10);
+  const table = arrow.tableFromArrays({
+    id: ids,
+    name: Array.from(ids, (id) => `name-${id}`),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(100)
+    .setBloomFilterEnabled(true)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  it("finds the row group containing a value", async () => {
+    expect(Array.from(await file.bloomFilterContains("id", 1230))).toContain(1);
+    expect(
+      Array.from(await file.bloomFilterContains("name", "name-3990"))
+    ).toContain(3);
+  });
+
+  it("excludes row groups that do not contain a value", async () => {
+    // No row group contains this value, but each one may report a false positive
+    const rowGroups = await file.bloomFilterContains("id", 1235);
+    expect(rowGroups.length).toBeLessThan(4);
+  });
+
+  it("only checks the requested row groups", async () => {
+    const rowGroups = await file.bloomFilterContains(
+      "id",
+      1230,
+      new Uint32Array([0, 1])
+    );
+    expect(Array.from(rowGroups)).toStrictEqual([1]);
+  });
+
+  it("errors on an unknown column", async () => {
+    await expect(file.bloomFilterContains("missing", 1)).rejects.toThrowError(
+      "Column missing not found in table"
+    );
+  });
+
+  it("is used by equality filters", async () => {
+    const result = arrow.tableFromIPC(
+      (
+        await file.read({
+          filter: { op: "in", column: "name", values: ["name-10", "name-2000"] },
+        })
+      ).intoIPCStream()
+    );
+    expect(result.getChild("id")!.toJSON()).toStrictEqual([10, 2000]);
+  });
+});
+
+describe("bloom filter on a decimal column", async (t) => {
+  // Four row groups of 100 prices each, from 10.00 to 13.99. With a precision of 9, the unscaled
+  // values are stored as INT32. Decimal128 values are four little-endian 32-bit words.
+  const unscaled = Uint32Array.from({ length: 1600 }, (_, i) =>
+    i % 4 === 0 ? 1000 + i / 4 : 0
+  );
+  const table = new arrow.Table({
+    price: arrow.makeVector(
+      arrow.makeData({
+        type: new arrow.Decimal(2, 9, 128),
+        length: 400,
+        data: unscaled,
+      })
+    ),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(100)
+    .setBloomFilterEnabled(true)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  it("checks the unscaled value", async () => {
+    expect(
+      Array.from(await file.bloomFilterContains("price", 12.34))
+    ).toContain(2);
+  });
+
+  it("is used by equality filters", async () => {
+    const result = arrow.tableFromIPC(
+      (
+        await file.read({ filter: { op: "==", column: "price", value: 12.34 } })
+      ).intoIPCStream()
+    );
+    expect(result.numRows).toStrictEqual(1);
+  });
+});
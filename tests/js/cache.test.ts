@@ -0,0 +1,76 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+const testFile = "1-partition-none.parquet";
+
+describe("byte range cache", async (t) => {
+  const expectedTable = readExpectedArrowData();
+
+  it("serves repeated reads from the cache", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+
+    const file = await wasm.ParquetFile.fromUrl(url, {
+      cacheSize: 16 * 1024 * 1024,
+    });
+    requestedRanges.length = 0;
+    let table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    expect(requestedRanges.length).toBeGreaterThan(0);
+    const misses = file.cacheStats().misses;
+    expect(misses).toBeGreaterThan(0);
+    expect(file.cacheStats().bytes).toBeGreaterThan(0);
+
+    // Reading the same or fewer columns again does not fetch anything
+    requestedRanges.length = 0;
+    table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    const column = expectedTable.schema.fields[0].name;
+    const projected = tableFromIPC(
+      (await file.read({ columns: [column] })).intoIPCStream()
+    );
+    expect(projected.numRows).toStrictEqual(expectedTable.numRows);
+    expect(requestedRanges).toStrictEqual([]);
+    expect(file.cacheStats().misses).toStrictEqual(misses);
+    expect(file.cacheStats().hits).toBeGreaterThan(0);
+
+    // After clearing the cache, the data is fetched again
+    file.clearCache();
+    expect(file.cacheStats().bytes).toStrictEqual(0);
+    expect(file.cacheStats().entries).toStrictEqual(0);
+    table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    expect(requestedRanges.length).toBeGreaterThan(0);
+
+    await server.close();
+  });
+
+  it("does not cache by default", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+
+    const file = await wasm.ParquetFile.fromUrl(url);
+    await file.read();
+    requestedRanges.length = 0;
+    await file.read();
+    expect(requestedRanges.length).toBeGreaterThan(0);
+    expect(file.cacheStats()).toStrictEqual({
+      hits: 0,
+      misses: 0,
+      bytes: 0,
+      entries: 0,
+    });
+
+    await server.close();
+  });
+});
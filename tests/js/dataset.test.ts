@@ -0,0 +1,256 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+function writeParquet(table: arrow.Table): Blob {
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+  return new Blob([buffer]);
+}
+
+describe("ParquetDataset", async (t) => {
+  const files = [
+    writeParquet(
+      new arrow.Table({
+        id: arrow.vectorFromArray([1, 2], new arrow.Int32()),
+        name: arrow.vectorFromArray(["a", "b"], new arrow.Utf8()),
+      })
+    ),
+    writeParquet(
+      new arrow.Table({
+        id: arrow.vectorFromArray([3n, 4n, 5n], new arrow.Int64()),
+        score: arrow.vectorFromArray([0.5, 1.5, 2.5], new arrow.Float64()),
+      })
+    ),
+  ];
+  const dataset = await wasm.ParquetDataset.fromFiles(files);
+
+  async function read(options?: wasm.ReaderOptions): Promise<arrow.Table> {
+    return arrow.tableFromIPC((await dataset.read(options)).intoIPCStream());
+  }
+
+  it("unifies schemas", () => {
+    expect(dataset.numFiles).toStrictEqual(2);
+    const schema = arrow.tableFromIPC(dataset.schema().intoIPCStream()).schema;
+    expect(schema.fields.map((f) => f.name)).toStrictEqual([
+      "id",
+      "name",
+      "score",
+    ]);
+    expect(schema.fields[0].type.bitWidth).toStrictEqual(64);
+  });
+
+  it("fills missing columns with nulls", async () => {
+    const table = await read();
+    expect(table.numRows).toStrictEqual(5);
+    expect(table.getChild("id")!.toJSON()).toStrictEqual([1n, 2n, 3n, 4n, 5n]);
+    expect(table.getChild("name")!.toJSON()).toStrictEqual([
+      "a",
+      "b",
+      null,
+      null,
+      null,
+    ]);
+  });
+
+  it("projects columns present in some files", async () => {
+    const table = await read({ columns: ["score"] });
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual(["score"]);
+    expect(table.getChild("score")!.toJSON()).toStrictEqual([
+      null,
+      null,
+      0.5,
+      1.5,
+      2.5,
+    ]);
+  });
+
+  it("errors on a column missing from all files", async () => {
+    await expect(read({ columns: ["missing"] })).rejects.toThrowError(
+      "Column missing not found in table"
+    );
+  });
+
+  it("treats missing columns as null in filters", async () => {
+    const isNull = await read({ filter: { op: "isNull", column: "name" } });
+    expect(isNull.getChild("id")!.toJSON()).toStrictEqual([3n, 4n, 5n]);
+
+    const gt = await read({ filter: { op: ">", column: "score", value: 1 } });
+    expect(gt.getChild("id")!.toJSON()).toStrictEqual([4n, 5n]);
+  });
+
+  it("applies offset and limit across files", async () => {
+    const table = await read({ offset: 1, limit: 3 });
+    expect(table.getChild("id")!.toJSON()).toStrictEqual([2n, 3n, 4n]);
+  });
+
+  it("streams all files", async () => {
+    const stream = (await dataset.stream()) as ReadableStream<wasm.RecordBatch>;
+    let numRows = 0;
+    for await (const batch of stream) {
+      numRows += arrow.tableFromIPC(batch.intoIPCStream()).numRows;
+    }
+    expect(numRows).toStrictEqual(5);
+  });
+});
+
+it("does not merge 64-bit integers with floats", async () => {
+  const files = [
+    writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray([2n ** 53n + 1n], new arrow.Int64()),
+      })
+    ),
+    writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray([0.5], new arrow.Float64()),
+      })
+    ),
+  ];
+  await expect(wasm.ParquetDataset.fromFiles(files)).rejects.toThrowError(
+    "Cannot unify types Int64 and Float64 of column value"
+  );
+});
+
+it("merges 32-bit integers with floats", async () => {
+  const files = [
+    writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray([1], new arrow.Int32()),
+      })
+    ),
+    writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray([0.5], new arrow.Float64()),
+      })
+    ),
+  ];
+  const dataset = await wasm.ParquetDataset.fromFiles(files);
+  const table = arrow.tableFromIPC((await dataset.read()).intoIPCStream());
+  expect(table.getChild("value")!.toJSON()).toStrictEqual([1, 0.5]);
+});
+
+describe("ParquetDataset with Hive partitioning", async (t) => {
+  function partitionFile(path: string, values: number[]): File {
+    const blob = writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray(values, new arrow.Int32()),
+      })
+    );
+    return new File([blob], path);
+  }
+
+  const files = [
+    partitionFile("data/year=2023/region=us%20east/part-0.parquet", [1, 2]),
+    partitionFile("data/year=2024/region=eu/part-0.parquet", [3]),
+  ];
+  const dataset = await wasm.ParquetDataset.fromFiles(files);
+
+  async function read(options?: wasm.ReaderOptions): Promise<arrow.Table> {
+    return arrow.tableFromIPC((await dataset.read(options)).intoIPCStream());
+  }
+
+  it("adds partition columns", async () => {
+    const table = await read();
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual([
+      "value",
+      "year",
+      "region",
+    ]);
+    expect(arrow.DataType.isInt(table.getChild("year")!.type)).toBeTruthy();
+    expect(table.getChild("year")!.toJSON()).toStrictEqual([2023, 2023, 2024]);
+    expect(table.getChild("region")!.toJSON()).toStrictEqual([
+      "us east",
+      "us east",
+      "eu",
+    ]);
+  });
+
+  it("projects partition columns", async () => {
+    const table = await read({ columns: ["region"] });
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual(["region"]);
+    expect(table.numRows).toStrictEqual(3);
+  });
+
+  it("filters on partition columns", async () => {
+    const table = await read({
+      filter: { op: "==", column: "year", value: 2024 },
+    });
+    expect(table.getChild("value")!.toJSON()).toStrictEqual([3]);
+
+    const mixed = await read({
+      filter: {
+        op: "or",
+        filters: [
+          { op: "==", column: "region", value: "eu" },
+          { op: "==", column: "value", value: 1 },
+        ],
+      },
+    });
+    expect(mixed.getChild("value")!.toJSON()).toStrictEqual([1, 3]);
+  });
+
+  it("skips opening files excluded by the dataset filter", async () => {
+    const invalid = new File(
+      [new Uint8Array([1, 2, 3])],
+      "data/year=2025/region=eu/part-0.parquet"
+    );
+    const filtered = await wasm.ParquetDataset.fromFiles([...files, invalid], {
+      filter: { op: "<", column: "year", value: 2025 },
+    });
+    expect(filtered.numFiles).toStrictEqual(2);
+  });
+
+  it("reads columns stored in the files instead of partition values", async () => {
+    const blob = writeParquet(
+      new arrow.Table({
+        value: arrow.vectorFromArray([4], new arrow.Int32()),
+        year: arrow.vectorFromArray(["2022"], new arrow.Utf8()),
+      })
+    );
+    const withYear = await wasm.ParquetDataset.fromFiles([
+      ...files,
+      new File([blob], "data/year=2025/region=eu/part-0.parquet"),
+    ]);
+    const table = arrow.tableFromIPC(
+      (
+        await withYear.read({
+          filter: { op: "isNotNull", column: "year" },
+        })
+      ).intoIPCStream()
+    );
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual([
+      "value",
+      "year",
+      "region",
+    ]);
+    expect(table.getChild("value")!.toJSON()).toStrictEqual([4]);
+    expect(table.getChild("year")!.toJSON()).toStrictEqual(["2022"]);
+  });
+
+  it("keeps integers with a leading zero or sign as strings", async () => {
+    const zipCodes = await wasm.ParquetDataset.fromFiles([
+      partitionFile("data/zip=01234/month=07/part-0.parquet", [1]),
+      partitionFile("data/zip=98765/month=7/part-0.parquet", [2]),
+      partitionFile("data/zip=+4321/month=12/part-0.parquet", [3]),
+    ]);
+    const table = arrow.tableFromIPC((await zipCodes.read()).intoIPCStream());
+    expect(table.getChild("zip")!.toJSON()).toStrictEqual([
+      "01234",
+      "98765",
+      "+4321",
+    ]);
+    expect(table.getChild("month")!.toJSON()).toStrictEqual(["07", "7", "12"]);
+  });
+
+  it("can disable partitioning", async () => {
+    const unpartitioned = await wasm.ParquetDataset.fromFiles(files, {
+      hivePartitioning: false,
+    });
+    const schema = arrow.tableFromIPC(
+      unpartitioned.schema().intoIPCStream()
+    ).schema;
+    expect(schema.fields.map((f) => f.name)).toStrictEqual(["value"]);
+  });
+});
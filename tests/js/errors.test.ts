@@ -0,0 +1,76 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+describe("errors while reading asynchronously", async (t) => {
+  const table = arrow.tableFromArrays({
+    value: Int32Array.from({ length: 10 }, (_, i) => i),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(5)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+
+  // Overwrite the start of the first page, after the leading magic bytes, leaving the footer
+  // intact so that the file can still be opened
+  const corrupted = buffer.slice();
+  corrupted.fill(0xff, 4, 36);
+  const corruptedFile = await wasm.ParquetFile.fromFile(new Blob([corrupted]));
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  it("rejects the read promise", async () => {
+    await expect(corruptedFile.read()).rejects.toThrowError();
+  });
+
+  it("errors the stream", async () => {
+    const stream = (await corruptedFile.stream()) as ReadableStream<wasm.RecordBatch>;
+    const readAll = async () => {
+      for await (const batch of stream) {
+        batch.free();
+      }
+    };
+    await expect(readAll()).rejects.toThrowError();
+  });
+
+  it("rejects row groups that are not in the file", async () => {
+    await expect(file.read({ rowGroups: [999] })).rejects.toThrowError(
+      "Row group 999 out of bounds"
+    );
+    await expect(file.stream({ rowGroups: [999] })).rejects.toThrowError(
+      "Row group 999 out of bounds"
+    );
+  });
+
+  it("rejects row groups that are not in the file when filtering", async () => {
+    const options: wasm.ReaderOptions = {
+      rowGroups: [999],
+      filter: { op: ">", column: "value", value: 2 },
+      rowNumberColumn: "row",
+    };
+    expect(() => wasm.readParquet(buffer, options)).toThrowError(
+      "Row group 999 out of bounds"
+    );
+    await expect(file.read(options)).rejects.toThrowError(
+      "Row group 999 out of bounds"
+    );
+    await expect(file.stream(options)).rejects.toThrowError(
+      "Row group 999 out of bounds"
+    );
+  });
+
+  it("leaves the module usable", async () => {
+    // Row groups that are not corrupted can still be read from the same file
+    const rowGroup = arrow.tableFromIPC(
+      (await corruptedFile.read({ rowGroups: [1] })).intoIPCStream()
+    );
+    expect(rowGroup.getChild("value")!.toArray()).toStrictEqual(
+      Int32Array.from([5, 6, 7, 8, 9])
+    );
+
+    const read = arrow.tableFromIPC((await file.read()).intoIPCStream());
+    expect(read.numRows).toStrictEqual(10);
+  });
+});
@@ -0,0 +1,230 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import { temporaryServer } from "./utils";
+
+// Path from repo root
+const dataDir = "tests/data";
+const testFile = "2-partition-none.parquet";
+
+describe("row filter", async (t) => {
+  const arr = new Uint8Array(readFileSync(`${dataDir}/${testFile}`));
+
+  function readFiltered(filter: wasm.FilterExpression): arrow.Table {
+    return arrow.tableFromIPC(wasm.readParquet(arr, { filter }).intoIPCStream());
+  }
+
+  it("comparison", () => {
+    const table = readFiltered({ op: ">", column: "uint8", value: 2 });
+    expect(table.getChild("str")!.toJSON()).toStrictEqual(["c", "d"]);
+  });
+
+  it("in", () => {
+    const table = readFiltered({ op: "in", column: "str", values: ["a", "d"] });
+    expect(table.getChild("uint8")!.toJSON()).toStrictEqual([1, 4]);
+  });
+
+  it("startsWith", () => {
+    const table = readFiltered({ op: "startsWith", column: "str", value: "b" });
+    expect(table.numRows).toStrictEqual(1);
+  });
+
+  it("isNull", () => {
+    const table = readFiltered({ op: "isNull", column: "str" });
+    expect(table.numRows).toStrictEqual(0);
+    expect(table.schema.fields.length).toStrictEqual(4);
+  });
+
+  it("and / or / not", () => {
+    const table = readFiltered({
+      op: "and",
+      filters: [
+        { op: "==", column: "bool", value: true },
+        {
+          op: "or",
+          filters: [
+            { op: "==", column: "int32", value: 0 },
+            { op: "not", filter: { op: "<", column: "uint8", value: 2 } },
+          ],
+        },
+      ],
+    });
+    expect(table.getChild("str")!.toJSON()).toStrictEqual(["a", "b"]);
+  });
+
+  it("combined with columns and limit", () => {
+    const table = arrow.tableFromIPC(
+      wasm
+        .readParquet(arr, {
+          columns: ["str"],
+          filter: { op: "!=", column: "bool", value: true },
+          limit: 1,
+        })
+        .intoIPCStream()
+    );
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual(["str"]);
+    expect(table.getChild("str")!.toJSON()).toStrictEqual(["c"]);
+  });
+
+  it("compares floats with integer columns without truncating them", () => {
+    const lessThan = readFiltered({ op: "<", column: "uint8", value: 2.5 });
+    expect(lessThan.getChild("str")!.toJSON()).toStrictEqual(["a", "b"]);
+    const greaterThan = readFiltered({ op: ">", column: "uint8", value: 1.5 });
+    expect(greaterThan.getChild("str")!.toJSON()).toStrictEqual([
+      "b",
+      "c",
+      "d",
+    ]);
+    expect(
+      readFiltered({ op: "==", column: "uint8", value: 1.5 }).numRows
+    ).toStrictEqual(0);
+    expect(
+      readFiltered({ op: "<", column: "uint8", value: 300 }).numRows
+    ).toStrictEqual(4);
+  });
+
+  it("errors on a value that cannot be cast to the column type", () => {
+    expect(() =>
+      readFiltered({ op: "==", column: "int32", value: "abc" })
+    ).toThrowError("Cannot cast");
+  });
+
+  it("errors on an unknown column", () => {
+    expect(() =>
+      readFiltered({ op: "==", column: "missing", value: 1 })
+    ).toThrowError("Column missing not found in table");
+  });
+
+  it("ParquetFile.read and ParquetFile.stream", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const rootUrl = `http://localhost:${listeningPort}`;
+
+    const filter: wasm.FilterExpression = {
+      op: ">=",
+      column: "uint8",
+      value: 2,
+    };
+    const file = await wasm.ParquetFile.fromUrl(`${rootUrl}/${testFile}`);
+    const table = arrow.tableFromIPC(
+      (await file.read({ filter })).intoIPCStream()
+    );
+    expect(table.getChild("str")!.toJSON()).toStrictEqual(["b", "c", "d"]);
+
+    const stream = (await file.stream({
+      filter,
+    })) as unknown as wasm.RecordBatch[];
+    let numRows = 0;
+    for await (const batch of stream) {
+      numRows += batch.numRows;
+    }
+    expect(numRows).toStrictEqual(3);
+
+    await server.close();
+  });
+
+  it("skips row groups that cannot match", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const rootUrl = `http://localhost:${listeningPort}`;
+
+    const file = await wasm.ParquetFile.fromUrl(`${rootUrl}/${testFile}`);
+    expect(file.metadata().numRowGroups()).toStrictEqual(2);
+
+    // The statistics of both row groups exclude this value, so no data should be fetched
+    const filter: wasm.FilterExpression = { op: "==", column: "str", value: "z" };
+
+    requestedRanges.length = 0;
+    const table = arrow.tableFromIPC(
+      (await file.read({ filter })).intoIPCStream()
+    );
+    expect(table.numRows).toStrictEqual(0);
+    expect(requestedRanges).toStrictEqual([]);
+
+    const stream = (await file.stream({
+      filter,
+    })) as unknown as wasm.RecordBatch[];
+    let numBatches = 0;
+    for await (const _batch of stream) {
+      numBatches += 1;
+    }
+    expect(numBatches).toStrictEqual(0);
+    expect(requestedRanges).toStrictEqual([]);
+
+    await server.close();
+  });
+
+  it("only fetches data pages that can match", async () => {
+    const numRows = 10_000;
+    const ids = Int32Array.from({ length: numRows }, (_, i) => i);
+    const table = arrow.tableFromArrays({
+      id: ids,
+      value: Float64Array.from(ids, (i) => i * 0.5),
+    });
+    // Write many small pages so that the page index is useful
+    const writerProperties = new wasm.WriterPropertiesBuilder()
+      .setDictionaryEnabled(false)
+      .setDataPageSizeLimit(1024)
+      .setWriteBatchSize(128)
+      .build();
+    const buffer = wasm.writeParquet(
+      wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+      writerProperties
+    );
+
+    const slicedBytes: number[] = [];
+    class RecordingBlob extends Blob {
+      slice(start?: number, end?: number, contentType?: string): Blob {
+        slicedBytes.push((end ?? this.size) - (start ?? 0));
+        return super.slice(start, end, contentType);
+      }
+    }
+    const sum = (values: number[]) => values.reduce((a, b) => a + b, 0);
+
+    const file = await wasm.ParquetFile.fromFile(new RecordingBlob([buffer]));
+
+    slicedBytes.length = 0;
+    await file.read();
+    const fullReadBytes = sum(slicedBytes);
+
+    slicedBytes.length = 0;
+    const filtered = arrow.tableFromIPC(
+      (
+        await file.read({ filter: { op: "==", column: "id", value: 5000 } })
+      ).intoIPCStream()
+    );
+    expect(filtered.getChild("value")!.toJSON()).toStrictEqual([2500]);
+    expect(sum(slicedBytes)).toBeLessThan(fullReadBytes / 10);
+
+    // The offset and limit select pages without a filter
+    slicedBytes.length = 0;
+    const sliced = arrow.tableFromIPC(
+      (await file.read({ offset: 5000, limit: 5 })).intoIPCStream()
+    );
+    expect(sliced.getChild("id")!.toJSON()).toStrictEqual([
+      5000, 5001, 5002, 5003, 5004,
+    ]);
+    expect(sum(slicedBytes)).toBeLessThan(fullReadBytes / 10);
+
+    // With a filter, they narrow down the pages selected by the page index
+    const filter: wasm.FilterExpression = { op: ">=", column: "id", value: 5000 };
+    slicedBytes.length = 0;
+    await file.read({ filter });
+    const filterBytes = sum(slicedBytes);
+    expect(filterBytes).toBeLessThan(fullReadBytes);
+
+    slicedBytes.length = 0;
+    const filteredPage = arrow.tableFromIPC(
+      (await file.read({ filter, offset: 10, limit: 5 })).intoIPCStream()
+    );
+    expect(filteredPage.getChild("id")!.toJSON()).toStrictEqual([
+      5010, 5011, 5012, 5013, 5014,
+    ]);
+    expect(filteredPage.getChild("value")!.toJSON()).toStrictEqual([
+      2505, 2505.5, 2506, 2506.5, 2507,
+    ]);
+    expect(sum(slicedBytes)).toBeLessThan(filterBytes);
+  });
+});
@@ -0,0 +1,66 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+const testFiles = ["1-partition-none.parquet", "2-partition-none.parquet"];
+
+describe("footer prefetch", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const footerPrefetchBytes = 1024 * 1024;
+
+  it("opens a file with a single request", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFiles[0]}`;
+
+    await wasm.ParquetFile.fromUrl(url);
+    expect(requestedRanges.length).toBeGreaterThan(1);
+
+    requestedRanges.length = 0;
+    const file = await wasm.ParquetFile.fromUrl(url, { footerPrefetchBytes });
+    expect(requestedRanges).toStrictEqual([`bytes=-${footerPrefetchBytes}`]);
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    await server.close();
+  });
+
+  it("fetches the rest of the footer if it does not fit", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFiles[0]}`;
+
+    const file = await wasm.ParquetFile.fromUrl(url, {
+      footerPrefetchBytes: 16,
+    });
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    await server.close();
+  });
+
+  it("opens the files of a dataset with a single request each", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const urls = testFiles.map(
+      (testFile) => `http://localhost:${listeningPort}/${testFile}`
+    );
+
+    const dataset = await wasm.ParquetDataset.fromUrls(urls, {
+      footerPrefetchBytes,
+    });
+    expect(dataset.numFiles).toStrictEqual(testFiles.length);
+    expect(requestedRanges).toStrictEqual(
+      testFiles.map(() => `bytes=-${footerPrefetchBytes}`)
+    );
+
+    await server.close();
+  });
+});
@@ -0,0 +1,71 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import { readExpectedArrowData, testArrowTablesEqual } from "./utils";
+
+const dataDir = "tests/data";
+
+describe("ParquetFile.fromBuffer", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const arr = new Uint8Array(
+    readFileSync(`${dataDir}/2-partition-none.parquet`)
+  );
+
+  it("reads a Uint8Array", async () => {
+    const file = await wasm.ParquetFile.fromBuffer(arr, { concurrency: 2 });
+    expect(file.metadata().numRowGroups()).toStrictEqual(2);
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+  });
+
+  it("streams the file", async () => {
+    const file = await wasm.ParquetFile.fromBuffer(arr);
+    const stream = (await file.stream()) as ReadableStream<wasm.RecordBatch>;
+    let numRows = 0;
+    for await (const batch of stream) {
+      numRows += tableFromIPC(batch.intoIPCStream()).numRows;
+    }
+    expect(numRows).toStrictEqual(expectedTable.numRows);
+  });
+
+  it("reads a ParquetBuffer", async () => {
+    const buffer = wasm.allocParquetBuffer(arr.length);
+    buffer.view().set(arr);
+    const file = await wasm.ParquetFile.fromParquetBuffer(buffer);
+    const table = tableFromIPC(
+      (await file.read({ offset: 1, limit: 2 })).intoIPCStream()
+    );
+    expect(table.numRows).toStrictEqual(2);
+    buffer.free();
+  });
+
+  it("makes a ParquetBuffer read-only once read", async () => {
+    const buffer = wasm.allocParquetBuffer(arr.length);
+    buffer.view().set(arr);
+    const file = await wasm.ParquetFile.fromParquetBuffer(buffer);
+    expect(() => buffer.view().fill(0)).toThrowError(
+      "can no longer be written to"
+    );
+    buffer.free();
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+  });
+
+  it("reads an ArrayBuffer", async () => {
+    const file = await wasm.ParquetFile.fromBuffer(arr.slice().buffer);
+    expect(file.metadata().numRowGroups()).toStrictEqual(2);
+  });
+
+  it("rejects values that are not Parquet data", async () => {
+    await expect(
+      wasm.ParquetFile.fromBuffer({} as wasm.ParquetInput)
+    ).rejects.toThrowError("Expected a Uint8Array");
+  });
+
+  it("rejects data that is not a Parquet file", async () => {
+    await expect(
+      wasm.ParquetFile.fromBuffer(new Uint8Array([1, 2, 3]))
+    ).rejects.toThrowError();
+  });
+});
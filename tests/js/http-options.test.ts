@@ -0,0 +1,80 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+describe("fromUrl request options", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const token = "Bearer secret";
+
+  async function withServer(test: (rootUrl: string) => Promise<void>) {
+    const server = await temporaryServer({
+      requiredHeaders: { Authorization: token },
+    });
+    const listeningPort = server.addresses()[0].port;
+    try {
+      await test(`http://localhost:${listeningPort}`);
+    } finally {
+      await server.close();
+    }
+  }
+
+  it("fails without the required headers", async () => {
+    await withServer(async (rootUrl) => {
+      await expect(
+        wasm.ParquetFile.fromUrl(`${rootUrl}/1-partition-none.parquet`)
+      ).rejects.toThrowError();
+    });
+  });
+
+  it("sends static headers with every request", async () => {
+    await withServer(async (rootUrl) => {
+      const file = await wasm.ParquetFile.fromUrl(
+        `${rootUrl}/1-partition-none.parquet`,
+        { headers: { Authorization: token }, credentials: "include" }
+      );
+      const table = tableFromIPC((await file.read()).intoIPCStream());
+      testArrowTablesEqual(expectedTable, table);
+    });
+  });
+
+  it("calls prepareRequest before every request", async () => {
+    await withServer(async (rootUrl) => {
+      const requests: wasm.FileRequest[] = [];
+      const file = await wasm.ParquetFile.fromUrl(`${rootUrl}/expired-url`, {
+        // The refreshed header replaces the static one
+        headers: { Authorization: "Bearer expired" },
+        prepareRequest: async (request) => {
+          requests.push(request);
+          return {
+            url: `${rootUrl}/1-partition-none.parquet`,
+            headers: { authorization: token },
+          };
+        },
+      });
+      const table = tableFromIPC((await file.read()).intoIPCStream());
+      testArrowTablesEqual(expectedTable, table);
+
+      // At least one request for the footer and one for the data
+      expect(requests.length).toBeGreaterThan(1);
+      expect(requests[0].url).toStrictEqual(`${rootUrl}/expired-url`);
+      expect(requests[0].range).toMatch(/^bytes=/);
+    });
+  });
+
+  it("rejects when prepareRequest throws", async () => {
+    await withServer(async (rootUrl) => {
+      await expect(
+        wasm.ParquetFile.fromUrl(`${rootUrl}/1-partition-none.parquet`, {
+          prepareRequest: () => {
+            throw new Error("token refresh failed");
+          },
+        })
+      ).rejects.toThrowError("token refresh failed");
+    });
+  });
+});
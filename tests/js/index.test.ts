@@ -6,3 +6,23 @@ import "./read-write.test";
 import "./ffi.test";
 import "./geo-metadata.test";
 import "./schema.test";
+import "./projection.test";
+import "./filter.test";
+import "./bloom-filter.test";
+import "./type-coercion.test";
+import "./dataset.test";
+import "./target-schema.test";
+import "./row-numbers.test";
+import "./stream.test";
+import "./errors.test";
+import "./abort.test";
+import "./progress.test";
+import "./http-options.test";
+import "./retry.test";
+import "./io-options.test";
+import "./cache.test";
+import "./persistent-cache.test";
+import "./metadata.test";
+import "./footer-prefetch.test";
+import "./reader.test";
+import "./from-buffer.test";
@@ -0,0 +1,112 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+const dataDir = "tests/data";
+const testFile = "1-partition-none.parquet";
+
+/** The number of bytes of a `Range` header with a start and an end */
+function rangeLength(range: string): number | undefined {
+  const match = /^bytes=(\d+)-(\d+)$/.exec(range);
+  return match ? Number(match[2]) - Number(match[1]) + 1 : undefined;
+}
+
+describe("I/O options", async (t) => {
+  const expectedTable = readExpectedArrowData();
+
+  it("splits requests larger than maxRequestSize", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+
+    const file = await wasm.ParquetFile.fromUrl(url, {
+      maxRequestSize: 100,
+      maxConcurrentRequests: 2,
+    });
+    requestedRanges.length = 0;
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    const lengths = requestedRanges.map(rangeLength);
+    expect(lengths.length).toBeGreaterThan(0);
+    for (const length of lengths) {
+      expect(length).toBeLessThanOrEqual(100);
+    }
+
+    await server.close();
+  });
+
+  it("coalesces ranges separated by at most coalesceSize", async () => {
+    const requestCounts: number[] = [];
+    for (const coalesceSize of [0, 1024 * 1024]) {
+      const requestedRanges: string[] = [];
+      const server = await temporaryServer({ requestedRanges });
+      const listeningPort = server.addresses()[0].port;
+      const url = `http://localhost:${listeningPort}/${testFile}`;
+
+      const file = await wasm.ParquetFile.fromUrl(url, { coalesceSize });
+      requestedRanges.length = 0;
+      const table = tableFromIPC((await file.read()).intoIPCStream());
+      testArrowTablesEqual(expectedTable, table);
+      requestCounts.push(requestedRanges.length);
+
+      await server.close();
+    }
+    expect(requestCounts[0]).toBeGreaterThan(requestCounts[1]);
+  });
+
+  it("applies the options of fromFile", async () => {
+    const buffer = readFileSync(`${dataDir}/${testFile}`);
+    const file = await wasm.ParquetFile.fromFile(new Blob([buffer]), {
+      coalesceSize: 0,
+      maxRequestSize: 64,
+      maxConcurrentRequests: 1,
+      concurrency: 4,
+    });
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    const stream = (await file.stream()) as ReadableStream<wasm.RecordBatch>;
+    let numRows = 0;
+    for await (const batch of stream) {
+      numRows += tableFromIPC(batch.intoIPCStream()).numRows;
+    }
+    expect(numRows).toStrictEqual(expectedTable.numRows);
+  });
+
+  it("applies the options of fromReader to getByteRanges", async () => {
+    const buffer = new Uint8Array(readFileSync(`${dataDir}/${testFile}`));
+    const calls: [number, number][][] = [];
+    const file = await wasm.ParquetFile.fromReader(
+      {
+        size: buffer.byteLength,
+        async getBytes(start: number, end: number) {
+          return buffer.slice(start, end);
+        },
+        async getByteRanges(ranges: [number, number][]) {
+          calls.push(ranges);
+          return ranges.map(([start, end]) => buffer.slice(start, end));
+        },
+      },
+      { maxRequestSize: 64, maxConcurrentRequests: 2 }
+    );
+    calls.length = 0;
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    expect(calls.length).toBeGreaterThan(0);
+    for (const ranges of calls) {
+      expect(ranges.length).toBeLessThanOrEqual(2);
+      for (const [start, end] of ranges) {
+        expect(end - start).toBeLessThanOrEqual(64);
+      }
+    }
+  });
+});
@@ -0,0 +1,112 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { tableFromIPC } from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+const dataDir = "tests/data";
+const testFile = "1-partition-none.parquet";
+
+describe("serialized metadata", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const buffer = readFileSync(`${dataDir}/${testFile}`);
+
+  it("opens a URL without fetching the footer", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+
+    const opened = await wasm.ParquetFile.fromUrl(url);
+    const metadata = opened.metadata().toBytes();
+    expect(metadata).toBeInstanceOf(Uint8Array);
+
+    requestedRanges.length = 0;
+    const file = await wasm.ParquetFile.fromUrl(url, { metadata });
+    expect(requestedRanges).toStrictEqual([]);
+    expect(file.metadata().numRowGroups()).toStrictEqual(
+      opened.metadata().numRowGroups()
+    );
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    for (const range of requestedRanges) {
+      expect(range.startsWith("bytes=-")).toBeFalsy();
+    }
+
+    await server.close();
+  });
+
+  it("opens a Blob with serialized metadata", async () => {
+    const metadata = wasm.readMetadata(buffer).toBytes();
+    const file = await wasm.ParquetFile.fromFile(new Blob([buffer]), {
+      metadata,
+    });
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+  });
+
+  it("keeps the page index", async () => {
+    const ids = Int32Array.from({ length: 10_000 }, (_, i) => i);
+    // Write many small pages so that the page index is used to skip pages
+    const writerProperties = new wasm.WriterPropertiesBuilder()
+      .setDictionaryEnabled(false)
+      .setDataPageSizeLimit(1024)
+      .setWriteBatchSize(128)
+      .build();
+    const pagedBuffer = wasm.writeParquet(
+      wasm.Table.fromIPCStream(
+        arrow.tableToIPC(arrow.tableFromArrays({ id: ids }), "stream")
+      ),
+      writerProperties
+    );
+
+    const slicedRanges: [number, number][] = [];
+    class RecordingBlob extends Blob {
+      slice(start?: number, end?: number, contentType?: string): Blob {
+        slicedRanges.push([start ?? 0, end ?? this.size]);
+        return super.slice(start, end, contentType);
+      }
+    }
+    const filter: wasm.FilterExpression = {
+      op: "==",
+      column: "id",
+      value: 5000,
+    };
+    async function filteredRanges(file: wasm.ParquetFile) {
+      slicedRanges.length = 0;
+      const table = tableFromIPC((await file.read({ filter })).intoIPCStream());
+      expect(table.getChild("id")!.toJSON()).toStrictEqual([5000]);
+      return [...slicedRanges];
+    }
+
+    const opened = await wasm.ParquetFile.fromFile(
+      new RecordingBlob([pagedBuffer])
+    );
+    const metadata = opened.metadata().toBytes();
+    const reopened = await wasm.ParquetFile.fromFile(
+      new RecordingBlob([pagedBuffer]),
+      { metadata }
+    );
+    const ranges = await filteredRanges(opened);
+    const fetchedBytes = ranges.reduce(
+      (sum, [start, end]) => sum + end - start,
+      0
+    );
+    expect(fetchedBytes).toBeLessThan(pagedBuffer.length / 10);
+    // Without the page index, all pages of the column would be fetched
+    expect(await filteredRanges(reopened)).toStrictEqual(ranges);
+  });
+
+  it("rejects invalid metadata", async () => {
+    await expect(
+      wasm.ParquetFile.fromFile(new Blob([buffer]), {
+        metadata: new Uint8Array([1, 2, 3]),
+      })
+    ).rejects.toThrowError();
+  });
+});
@@ -0,0 +1,80 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  temporaryServer,
+  testArrowTablesEqual,
+} from "./utils";
+
+const testFile = "1-partition-none.parquet";
+
+/** A cache adapter backed by a Map */
+function mapAdapter(store: Map<string, Uint8Array>): wasm.RangeCacheAdapter {
+  return {
+    get: async (key) => store.get(key),
+    put: async (key, bytes) => {
+      store.set(key, bytes);
+    },
+  };
+}
+
+/** Wait for the cache writes made in the background */
+async function flushWrites() {
+  await new Promise((resolve) => setTimeout(resolve, 0));
+}
+
+describe("persistent range cache", async (t) => {
+  const expectedTable = readExpectedArrowData();
+
+  it("reads the ranges of a reopened file from the cache", async () => {
+    const requestedRanges: string[] = [];
+    const server = await temporaryServer({ requestedRanges });
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+    const store = new Map<string, Uint8Array>();
+
+    let file = await wasm.ParquetFile.fromUrl(url, {
+      cacheAdapter: mapAdapter(store),
+    });
+    let table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    await flushWrites();
+    expect(store.size).toBeGreaterThan(0);
+    for (const key of store.keys()) {
+      expect(key.startsWith(url)).toBeTruthy();
+    }
+
+    // Only the ETag is requested when the file is opened again
+    requestedRanges.length = 0;
+    file = await wasm.ParquetFile.fromUrl(url, {
+      cacheAdapter: mapAdapter(store),
+    });
+    table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    expect(requestedRanges).toStrictEqual(["bytes=0-0"]);
+
+    await server.close();
+  });
+
+  it("falls back to the network when the adapter fails", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/${testFile}`;
+
+    const file = await wasm.ParquetFile.fromUrl(url, {
+      cacheAdapter: {
+        get: async () => {
+          throw new Error("quota exceeded");
+        },
+        put: async () => {
+          throw new Error("quota exceeded");
+        },
+      },
+    });
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+
+    await server.close();
+  });
+});
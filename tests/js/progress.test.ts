@@ -0,0 +1,74 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import { temporaryServer } from "./utils";
+
+describe("report the progress of async reads", async (t) => {
+  // Four row groups of five rows each
+  const table = arrow.tableFromArrays({
+    value: Int32Array.from({ length: 20 }, (_, i) => i),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(5)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  it("reports bytes, row groups and rows", async () => {
+    const updates: wasm.ReadProgress[] = [];
+    await file.read({ onProgress: (progress) => updates.push(progress) });
+
+    const last = updates[updates.length - 1];
+    expect(last.rowGroupsTotal).toStrictEqual(4);
+    expect(last.rowGroupsCompleted).toStrictEqual(4);
+    expect(last.rowsDecoded).toStrictEqual(20);
+    expect(last.bytesRequested).toBeGreaterThan(0);
+    expect(last.bytesReceived).toStrictEqual(last.bytesRequested);
+
+    // Progress only ever increases
+    for (let i = 1; i < updates.length; i++) {
+      expect(updates[i].bytesReceived).toBeGreaterThanOrEqual(
+        updates[i - 1].bytesReceived
+      );
+      expect(updates[i].rowsDecoded).toBeGreaterThanOrEqual(
+        updates[i - 1].rowsDecoded
+      );
+    }
+  });
+
+  it("only counts the row groups that are read", async () => {
+    let last: wasm.ReadProgress | undefined;
+    const stream = (await file.stream({
+      offset: 6,
+      limit: 3,
+      onProgress: (progress) => (last = progress),
+    })) as ReadableStream<wasm.RecordBatch>;
+    for await (const batch of stream) {
+      batch.free();
+    }
+    expect(last!.rowGroupsTotal).toStrictEqual(1);
+    expect(last!.rowGroupsCompleted).toStrictEqual(1);
+    expect(last!.rowsDecoded).toStrictEqual(3);
+  });
+
+  it("reports the bytes of remote reads", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const url = `http://localhost:${listeningPort}/1-partition-none.parquet`;
+    const remoteFile = await wasm.ParquetFile.fromUrl(url);
+
+    let last: wasm.ReadProgress | undefined;
+    const read = arrow.tableFromIPC(
+      (
+        await remoteFile.read({ onProgress: (progress) => (last = progress) })
+      ).intoIPCStream()
+    );
+    expect(last!.rowsDecoded).toStrictEqual(read.numRows);
+    expect(last!.bytesReceived).toBeGreaterThan(0);
+    expect(last!.bytesReceived).toStrictEqual(last!.bytesRequested);
+    await server.close();
+  });
+});
@@ -0,0 +1,110 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import { temporaryServer, testArrowTablesEqual } from "./utils";
+
+// Path from repo root
+const dataDir = "tests/data";
+
+/** Create a Parquet buffer with a flat, a struct and a list column */
+function createNestedParquet(): Uint8Array {
+  const structType = new arrow.Struct([
+    new arrow.Field("a", new arrow.Int32(), true),
+    new arrow.Field("b", new arrow.Utf8(), true),
+  ]);
+  const listType = new arrow.List(
+    new arrow.Field("item", new arrow.Float64(), true)
+  );
+  const table = new arrow.Table({
+    id: arrow.vectorFromArray([1, 2, 3], new arrow.Int32()),
+    nested: arrow.vectorFromArray(
+      [
+        { a: 1, b: "x" },
+        { a: 2, b: "y" },
+        { a: 3, b: "z" },
+      ],
+      structType
+    ),
+    list: arrow.vectorFromArray([[1.5], [2.5, 3.5], []], listType),
+  });
+  return wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+}
+
+describe("column projection", async (t) => {
+  const parquetBuffer = createNestedParquet();
+
+  it("projects a flat column", () => {
+    const table = arrow.tableFromIPC(
+      wasm.readParquet(parquetBuffer, { columns: ["id"] }).intoIPCStream()
+    );
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual(["id"]);
+    expect(table.getChild("id")!.toJSON()).toStrictEqual([1, 2, 3]);
+  });
+
+  it("projects an entire struct column", () => {
+    const table = arrow.tableFromIPC(
+      wasm.readParquet(parquetBuffer, { columns: ["nested"] }).intoIPCStream()
+    );
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual(["nested"]);
+    const nested = table.getChild("nested")!;
+    expect(nested.type.children.map((f) => f.name)).toStrictEqual(["a", "b"]);
+  });
+
+  it("projects a single struct child", () => {
+    const table = arrow.tableFromIPC(
+      wasm
+        .readParquet(parquetBuffer, { columns: ["nested.b"] })
+        .intoIPCStream()
+    );
+    const nested = table.getChild("nested")!;
+    expect(nested.type.children.map((f) => f.name)).toStrictEqual(["b"]);
+    expect(nested.getChild("b")!.toJSON()).toStrictEqual(["x", "y", "z"]);
+  });
+
+  it("projects a list column", () => {
+    const table = arrow.tableFromIPC(
+      wasm
+        .readParquet(parquetBuffer, { columns: ["list", "id"] })
+        .intoIPCStream()
+    );
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual([
+      "id",
+      "list",
+    ]);
+    const list = table.getChild("list")!;
+    expect(list.get(1)!.toJSON()).toStrictEqual([2.5, 3.5]);
+    expect(list.get(2)!.length).toStrictEqual(0);
+  });
+
+  it("errors on an unknown column", () => {
+    expect(() =>
+      wasm.readParquet(parquetBuffer, { columns: ["missing"] })
+    ).toThrowError("Column missing not found in table");
+  });
+
+  it("matches ParquetFile.read", async () => {
+    const server = await temporaryServer();
+    const listeningPort = server.addresses()[0].port;
+    const rootUrl = `http://localhost:${listeningPort}`;
+
+    const testFile = "2-partition-none.parquet";
+    const columns = ["str", "bool"];
+    const arr = new Uint8Array(readFileSync(`${dataDir}/${testFile}`));
+    const syncTable = arrow.tableFromIPC(
+      wasm.readParquet(arr, { columns }).intoIPCStream()
+    );
+
+    const file = await wasm.ParquetFile.fromUrl(`${rootUrl}/${testFile}`);
+    const asyncTable = arrow.tableFromIPC(
+      (await file.read({ columns })).intoIPCStream()
+    );
+
+    expect(syncTable.schema.fields.map((f) => f.name)).toStrictEqual(columns);
+    testArrowTablesEqual(syncTable, asyncTable);
+
+    await server.close();
+  });
+});
@@ -1,4 +1,4 @@
-import { DataType, tableFromIPC, tableToIPC } from "apache-arrow";
+import { DataType, Table, tableFromIPC, tableToIPC } from "apache-arrow";
 import { readFileSync } from "fs";
 import { describe, expect, it } from "vitest";
 import * as wasm from "../../pkg/node/parquet_wasm";
@@ -38,6 +38,84 @@ describe("read file", async (t) => {
   }
 });
 
+describe("read file with ParquetReader", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const arr = new Uint8Array(
+    readFileSync(`${dataDir}/2-partition-none.parquet`)
+  );
+
+  it("yields record batches from next()", () => {
+    const reader = new wasm.ParquetReader(arr, { batchSize: 1 });
+    const batches = [];
+    let result = reader.next();
+    while (!result.done) {
+      batches.push(tableFromIPC(result.value.intoIPCStream()));
+      result = reader.next();
+    }
+    expect(batches.length).toStrictEqual(expectedTable.numRows);
+    testArrowTablesEqual(
+      expectedTable,
+      new Table(batches.flatMap((table) => table.batches))
+    );
+  });
+
+  it("supports JS iteration", () => {
+    const reader = new wasm.ParquetReader(arr, { columns: ["str"] });
+    const schema = tableFromIPC(reader.schema().intoIPCStream()).schema;
+    expect(schema.fields.map((f) => f.name)).toStrictEqual(["str"]);
+
+    const batches = [];
+    for (const batch of Iterator.from(reader)) {
+      batches.push(...tableFromIPC(batch.intoIPCStream()).batches);
+    }
+    expect(new Table(batches).getChild("str")!.toJSON()).toStrictEqual(
+      expectedTable.getChild("str")!.toJSON()
+    );
+  });
+});
+
+describe("read file from ParquetBuffer", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const arr = new Uint8Array(
+    readFileSync(`${dataDir}/2-partition-none.parquet`)
+  );
+  const buffer = wasm.allocParquetBuffer(arr.length);
+  buffer.view().set(arr);
+
+  it("readParquetBuffer", () => {
+    expect(buffer.length).toStrictEqual(arr.length);
+    const table = tableFromIPC(wasm.readParquetBuffer(buffer).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+  });
+
+  it("readSchemaBuffer and readMetadataBuffer", () => {
+    const schema = tableFromIPC(
+      wasm.readSchemaBuffer(buffer).intoIPCStream()
+    ).schema;
+    expect(schema.fields.length).toStrictEqual(4);
+    expect(wasm.readMetadataBuffer(buffer).numRowGroups()).toStrictEqual(2);
+  });
+
+  it("ParquetReader.fromParquetBuffer", () => {
+    const reader = wasm.ParquetReader.fromParquetBuffer(buffer);
+    expect(reader.next().done).toBeFalsy();
+  });
+
+  it("errors once freed", () => {
+    const freed = wasm.allocParquetBuffer(arr.length);
+    freed.view().set(arr);
+    freed.free();
+    expect(() => wasm.readParquetBuffer(freed)).toThrowError();
+  });
+
+  it("is not accepted in place of a Uint8Array", () => {
+    const input = buffer as unknown as Uint8Array;
+    expect(() => wasm.readParquet(input)).toThrowError(
+      "Expected a Uint8Array or an ArrayBuffer"
+    );
+  });
+});
+
 it("read-write-read round trip (with writer properties)", async (t) => {
   const dataPath = `${dataDir}/1-partition-brotli.parquet`;
   const buffer = readFileSync(dataPath);
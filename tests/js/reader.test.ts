@@ -0,0 +1,103 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { readFileSync } from "fs";
+import { describe, expect, it } from "vitest";
+import { readExpectedArrowData, testArrowTablesEqual } from "./utils";
+
+const dataDir = "tests/data";
+const testFile = "1-partition-none.parquet";
+
+/** A byte source reading from an in-memory buffer, recording the ranges read */
+function bufferSource(buffer: Uint8Array, requestedRanges: [number, number][]) {
+  return {
+    size: buffer.byteLength,
+    async getBytes(start: number, end: number) {
+      requestedRanges.push([start, end]);
+      return buffer.slice(start, end);
+    },
+  };
+}
+
+describe("ParquetFile.fromReader", async (t) => {
+  const expectedTable = readExpectedArrowData();
+  const buffer = new Uint8Array(readFileSync(`${dataDir}/${testFile}`));
+
+  it("reads a file from getBytes", async () => {
+    const requestedRanges: [number, number][] = [];
+    const file = await wasm.ParquetFile.fromReader(
+      bufferSource(buffer, requestedRanges)
+    );
+    expect(file.metadata().fileMetadata().numRows()).toBe(
+      expectedTable.numRows
+    );
+
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    for (const [start, end] of requestedRanges) {
+      expect(start).toBeLessThan(end);
+      expect(end).toBeLessThanOrEqual(buffer.byteLength);
+    }
+
+    const stream = (await file.stream()) as ReadableStream<wasm.RecordBatch>;
+    let numRows = 0;
+    for await (const batch of stream) {
+      numRows += tableFromIPC(batch.intoIPCStream()).numRows;
+    }
+    expect(numRows).toBe(expectedTable.numRows);
+  });
+
+  it("applies the I/O options to getBytes", async () => {
+    const requestedRanges: [number, number][] = [];
+    const file = await wasm.ParquetFile.fromReader(
+      bufferSource(buffer, requestedRanges),
+      { coalesceSize: 0, maxRequestSize: 64 }
+    );
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    for (const [start, end] of requestedRanges) {
+      expect(end - start).toBeLessThanOrEqual(64);
+    }
+  });
+
+  it("reads several ranges with getByteRanges", async () => {
+    const requestedRanges: [number, number][] = [];
+    const batches: [number, number][][] = [];
+    const file = await wasm.ParquetFile.fromReader({
+      ...bufferSource(buffer, requestedRanges),
+      async getByteRanges(ranges: [number, number][]) {
+        batches.push(ranges);
+        return ranges.map(([start, end]) => buffer.slice(start, end));
+      },
+    });
+    const table = tableFromIPC((await file.read()).intoIPCStream());
+    testArrowTablesEqual(expectedTable, table);
+    expect(batches.length).toBeGreaterThan(0);
+    expect(requestedRanges.length).toBe(0);
+  });
+
+  it("rejects with the error of getBytes", async () => {
+    const opening = wasm.ParquetFile.fromReader({
+      size: buffer.byteLength,
+      async getBytes() {
+        throw new Error("Storage unavailable");
+      },
+    });
+    await expect(opening).rejects.toThrowError("Storage unavailable");
+  });
+
+  it("rejects when getBytes returns the wrong number of bytes", async () => {
+    const opening = wasm.ParquetFile.fromReader({
+      size: buffer.byteLength,
+      async getBytes(start: number, end: number) {
+        return buffer.slice(start, end - 1);
+      },
+    });
+    await expect(opening).rejects.toThrowError("getBytes returned");
+  });
+
+  it("rejects an invalid source", async () => {
+    await expect(
+      wasm.ParquetFile.fromReader({ size: -1, getBytes: async () => buffer })
+    ).rejects.toThrowError("size must be a non-negative integer");
+  });
+});
@@ -0,0 +1,58 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import { tableFromIPC } from "apache-arrow";
+import { describe, expect, it } from "vitest";
+import {
+  readExpectedArrowData,
+  testArrowTablesEqual,
+  withServer,
+} from "./utils";
+
+describe("fromUrl retries", async (t) => {
+  const expectedTable = readExpectedArrowData();
+
+  /** Serve the test data, replying with `status` to the first `failures` requests. */
+  async function withFlakyServer(
+    failures: number,
+    status: number,
+    test: (url: string, requestCount: () => number) => Promise<void>
+  ) {
+    let requests = 0;
+    await withServer(
+      {
+        onRequest: async (request, reply) => {
+          requests += 1;
+          if (requests <= failures) {
+            reply.code(status).header("Retry-After", "0").send();
+            return reply;
+          }
+        },
+      },
+      (baseUrl) => test(`${baseUrl}/1-partition-none.parquet`, () => requests)
+    );
+  }
+
+  it("retries transient failures", async () => {
+    await withFlakyServer(2, 503, async (url, requestCount) => {
+      const file = await wasm.ParquetFile.fromUrl(url);
+      const table = tableFromIPC((await file.read()).intoIPCStream());
+      testArrowTablesEqual(expectedTable, table);
+      expect(requestCount()).toBeGreaterThan(2);
+    });
+  });
+
+  it("gives up after maxAttempts", async () => {
+    await withFlakyServer(2, 429, async (url, requestCount) => {
+      await expect(
+        wasm.ParquetFile.fromUrl(url, { retry: { maxAttempts: 2 } })
+      ).rejects.toThrowError();
+      expect(requestCount()).toStrictEqual(2);
+    });
+  });
+
+  it("does not retry client errors", async () => {
+    await withFlakyServer(1, 404, async (url, requestCount) => {
+      await expect(wasm.ParquetFile.fromUrl(url)).rejects.toThrowError();
+      expect(requestCount()).toStrictEqual(1);
+    });
+  });
+});
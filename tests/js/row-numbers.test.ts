@@ -0,0 +1,101 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+describe("row numbers", async (t) => {
+  // Four row groups of three rows each
+  const table = arrow.tableFromArrays({
+    value: Int32Array.from({ length: 12 }, (_, i) => i * 10),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(3)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  function rowNumbers(table: arrow.Table): number[] {
+    return Array.from(table.getChild("row")!.toArray(), Number);
+  }
+
+  function read(options: wasm.ReaderOptions): arrow.Table {
+    return arrow.tableFromIPC(
+      wasm.readParquet(buffer, { rowNumberColumn: "row", ...options })
+        .intoIPCStream()
+    );
+  }
+
+  it("appends a row number column", () => {
+    const table = read({});
+    expect(table.schema.fields.map((f) => f.name)).toStrictEqual([
+      "value",
+      "row",
+    ]);
+    expect(rowNumbers(table)).toStrictEqual([
+      0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11,
+    ]);
+  });
+
+  it("accounts for row groups, offset and limit", () => {
+    expect(rowNumbers(read({ offset: 2, limit: 5 }))).toStrictEqual([
+      2, 3, 4, 5, 6,
+    ]);
+    expect(rowNumbers(read({ rowGroups: [3, 1] }))).toStrictEqual([
+      9, 10, 11, 3, 4, 5,
+    ]);
+  });
+
+  it("accounts for filters", () => {
+    const table = read({
+      filter: {
+        op: "and",
+        filters: [
+          { op: ">=", column: "value", value: 20 },
+          { op: "!=", column: "value", value: 50 },
+        ],
+      },
+      offset: 1,
+      limit: 4,
+    });
+    expect(table.getChild("value")!.toJSON()).toStrictEqual([30, 40, 60, 70]);
+    expect(rowNumbers(table)).toStrictEqual([3, 4, 6, 7]);
+  });
+
+  it("ParquetReader", () => {
+    const reader = new wasm.ParquetReader(buffer, {
+      rowNumberColumn: "row",
+      batchSize: 2,
+    });
+    const rows = [];
+    for (const batch of Iterator.from(reader)) {
+      rows.push(...rowNumbers(arrow.tableFromIPC(batch.intoIPCStream())));
+    }
+    expect(rows).toStrictEqual([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+  });
+
+  it("ParquetFile.read and stream", async () => {
+    const options: wasm.ReaderOptions = {
+      rowNumberColumn: "row",
+      filter: { op: "in", column: "value", values: [10, 70, 110] },
+    };
+    const table = arrow.tableFromIPC(
+      (await file.read(options)).intoIPCStream()
+    );
+    expect(rowNumbers(table)).toStrictEqual([1, 7, 11]);
+
+    const stream = (await file.stream(options)) as ReadableStream<wasm.RecordBatch>;
+    const rows = [];
+    for await (const batch of stream) {
+      rows.push(...rowNumbers(arrow.tableFromIPC(batch.intoIPCStream())));
+    }
+    expect(rows).toStrictEqual([1, 7, 11]);
+  });
+
+  it("rejects a name that is already a column", () => {
+    expect(() => read({ rowNumberColumn: "value" })).toThrowError(
+      "Row number column value is also a column of the file"
+    );
+  });
+});
@@ -0,0 +1,62 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+describe("ParquetFile.stream offset and limit", async (t) => {
+  // Four row groups of five rows each
+  const table = arrow.tableFromArrays({
+    value: Int32Array.from({ length: 20 }, (_, i) => i),
+  });
+  const writerProperties = new wasm.WriterPropertiesBuilder()
+    .setMaxRowGroupSize(5)
+    .build();
+  const buffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream")),
+    writerProperties
+  );
+  const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+
+  async function streamValues(options: wasm.ReaderOptions): Promise<number[]> {
+    const stream = (await file.stream(options)) as ReadableStream<wasm.RecordBatch>;
+    const values = [];
+    for await (const batch of stream) {
+      const table = arrow.tableFromIPC(batch.intoIPCStream());
+      values.push(...table.getChild("value")!.toArray());
+    }
+    return values;
+  }
+
+  it("applies the limit to the whole file", async () => {
+    expect(await streamValues({ limit: 7 })).toStrictEqual([
+      0, 1, 2, 3, 4, 5, 6,
+    ]);
+  });
+
+  it("applies the offset to the whole file", async () => {
+    expect(await streamValues({ offset: 12, limit: 4 })).toStrictEqual([
+      12, 13, 14, 15,
+    ]);
+    expect(await streamValues({ offset: 18, concurrency: 4 })).toStrictEqual([
+      18, 19,
+    ]);
+    expect(await streamValues({ offset: 25 })).toStrictEqual([]);
+  });
+
+  it("applies the offset and limit after the filter", async () => {
+    const values = await streamValues({
+      filter: { op: "in", column: "value", values: [1, 3, 8, 11, 16, 19] },
+      offset: 2,
+      limit: 3,
+      concurrency: 2,
+    });
+    expect(values).toStrictEqual([8, 11, 16]);
+  });
+
+  it("matches ParquetFile.read", async () => {
+    const options = { offset: 3, limit: 9, rowGroups: [3, 0, 2] };
+    const read = arrow.tableFromIPC((await file.read(options)).intoIPCStream());
+    expect(await streamValues(options)).toStrictEqual(
+      Array.from(read.getChild("value")!.toArray())
+    );
+  });
+});
@@ -0,0 +1,144 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+describe("read with a target schema", async (t) => {
+  const table = new arrow.Table({
+    id: arrow.vectorFromArray([1, 2, 3], new arrow.Int32()),
+    name: arrow.vectorFromArray(["a", "b", "a"], new arrow.LargeUtf8()),
+    other: arrow.vectorFromArray([true, false, true], new arrow.Bool()),
+  });
+  const parquetBuffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+
+  function read(options: wasm.ReaderOptions): arrow.Table {
+    return arrow.tableFromIPC(
+      wasm.readParquet(parquetBuffer, options).intoIPCStream()
+    );
+  }
+
+  function checkTargetTypes(table: arrow.Table) {
+    const id = table.getChild("id")!;
+    expect(id.type.bitWidth).toStrictEqual(64);
+    expect(id.toJSON()).toStrictEqual([1n, 2n, 3n]);
+
+    const name = table.getChild("name")!;
+    expect(arrow.DataType.isDictionary(name.type)).toBeTruthy();
+    expect(name.toJSON()).toStrictEqual(["a", "b", "a"]);
+
+    // Columns that are not in the target schema keep their types
+    expect(arrow.DataType.isBool(table.getChild("other")!.type)).toBeTruthy();
+  }
+
+  it("from a schema description", () => {
+    checkTargetTypes(
+      read({
+        schema: {
+          fields: [
+            { name: "id", type: "Int64" },
+            { name: "name", type: "Dictionary(Int32, Utf8)" },
+          ],
+        },
+      })
+    );
+  });
+
+  it("from an Arrow IPC stream", () => {
+    const schema = new arrow.Schema([
+      new arrow.Field("id", new arrow.Int64()),
+      new arrow.Field(
+        "name",
+        new arrow.Dictionary(new arrow.Utf8(), new arrow.Int32())
+      ),
+    ]);
+    checkTargetTypes(
+      read({ schema: arrow.tableToIPC(new arrow.Table(schema), "stream") })
+    );
+  });
+
+  it("with ParquetFile", async () => {
+    const file = await wasm.ParquetFile.fromFile(new Blob([parquetBuffer]));
+    const wasmTable = await file.read({
+      schema: {
+        fields: [
+          { name: "id", type: "Int64" },
+          { name: "name", type: "Dictionary(Int32, Utf8)" },
+        ],
+      },
+    });
+    checkTargetTypes(arrow.tableFromIPC(wasmTable.intoIPCStream()));
+  });
+
+  it("rejects an invalid type", () => {
+    expect(() =>
+      read({ schema: { fields: [{ name: "id", type: "NotAType" }] } })
+    ).toThrowError();
+  });
+
+  it("skips the Arrow schema stored in the file", () => {
+    expect(read({}).getChild("name")!.type.typeId).toStrictEqual(
+      arrow.Type.LargeUtf8
+    );
+    expect(
+      read({ skipArrowMetadata: true }).getChild("name")!.type.typeId
+    ).toStrictEqual(arrow.Type.Utf8);
+  });
+});
+
+describe("read nested columns with a target schema", async (t) => {
+  const structType = new arrow.Struct([
+    new arrow.Field("a", new arrow.Int32(), true),
+    new arrow.Field("b", new arrow.Utf8(), true),
+  ]);
+  const table = new arrow.Table({
+    nested: arrow.vectorFromArray(
+      [
+        { a: 1, b: "x" },
+        { a: 2, b: "y" },
+      ],
+      structType
+    ),
+  });
+  table.schema.metadata.set("source", "test");
+  const parquetBuffer = wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+  const schema = {
+    fields: [
+      {
+        name: "nested",
+        type: "Struct(a Int64, b Utf8)",
+      },
+    ],
+  };
+
+  it("projects the target type to the children that are read", () => {
+    const projected = arrow.tableFromIPC(
+      wasm
+        .readParquet(parquetBuffer, { columns: ["nested.a"], schema })
+        .intoIPCStream()
+    );
+    const nested = projected.getChild("nested")!;
+    expect(nested.type.children.map((f) => f.name)).toStrictEqual(["a"]);
+    expect(nested.getChild("a")!.type.bitWidth).toStrictEqual(64);
+    expect(nested.getChild("a")!.toJSON()).toStrictEqual([1n, 2n]);
+  });
+
+  it("keeps the schema metadata of the file without target metadata", () => {
+    const read = arrow.tableFromIPC(
+      wasm.readParquet(parquetBuffer, { schema }).intoIPCStream()
+    );
+    expect(read.schema.metadata.get("source")).toStrictEqual("test");
+    expect(read.getChild("nested")!.type.children.length).toStrictEqual(2);
+
+    const withMetadata = arrow.tableFromIPC(
+      wasm
+        .readParquet(parquetBuffer, {
+          schema: { ...schema, metadata: { source: "target" } },
+        })
+        .intoIPCStream()
+    );
+    expect(withMetadata.schema.metadata.get("source")).toStrictEqual("target");
+  });
+});
@@ -0,0 +1,199 @@
+import * as wasm from "../../pkg/node/parquet_wasm";
+import * as arrow from "apache-arrow";
+import { describe, expect, it } from "vitest";
+
+/** Create a Parquet buffer with columns that Arrow JS handles poorly */
+function createParquet(): Uint8Array {
+  const table = new arrow.Table({
+    int64: arrow.vectorFromArray([1n, 2n, 3n], new arrow.Int64()),
+    timestamp: arrow.vectorFromArray(
+      [1_000, 2_500, 3_000],
+      new arrow.TimestampMillisecond()
+    ),
+    largeUtf8: arrow.vectorFromArray(["a", "b", "c"], new arrow.LargeUtf8()),
+    binary: arrow.vectorFromArray(
+      ["x", "y", "z"].map((s) => new TextEncoder().encode(s)),
+      new arrow.Binary()
+    ),
+  });
+  return wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+}
+
+/** Create a Parquet buffer with decimal, large and nested columns */
+function createNestedParquet(): Uint8Array {
+  // Decimal128 values are stored as four little-endian 32-bit words
+  const decimal = arrow.makeVector(
+    arrow.makeData({
+      type: new arrow.Decimal(2, 10, 128),
+      length: 2,
+      data: new Uint32Array([123, 0, 0, 0, 456, 0, 0, 0]),
+    })
+  );
+  const entries = new arrow.Struct([
+    new arrow.Field("key", new arrow.Utf8(), false),
+    new arrow.Field("value", new arrow.LargeUtf8(), true),
+  ]);
+  const table = new arrow.Table({
+    decimal,
+    largeBinary: arrow.vectorFromArray(
+      ["x", "y"].map((s) => new TextEncoder().encode(s)),
+      new arrow.LargeBinary()
+    ),
+    map: arrow.vectorFromArray(
+      [new Map([["k", "a"]]), new Map([["k", "b"]])],
+      new arrow.Map_(new arrow.Field("entries", entries, false))
+    ),
+    dictionary: arrow.vectorFromArray(
+      ["a", "b"],
+      new arrow.Dictionary(new arrow.LargeUtf8(), new arrow.Int32())
+    ),
+  });
+  return wasm.writeParquet(
+    wasm.Table.fromIPCStream(arrow.tableToIPC(table, "stream"))
+  );
+}
+
+/** Read a Parquet buffer with each of the reading APIs */
+const readers: [
+  string,
+  (buffer: Uint8Array, options: wasm.ReaderOptions) => Promise<arrow.Table>
+][] = [
+  [
+    "readParquet",
+    async (buffer, options) =>
+      arrow.tableFromIPC(wasm.readParquet(buffer, options).intoIPCStream()),
+  ],
+  [
+    "ParquetFile.read",
+    async (buffer, options) => {
+      const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+      return arrow.tableFromIPC((await file.read(options)).intoIPCStream());
+    },
+  ],
+  [
+    "ParquetFile.stream",
+    async (buffer, options) => {
+      const file = await wasm.ParquetFile.fromFile(new Blob([buffer]));
+      const stream = (await file.stream(
+        options
+      )) as ReadableStream<wasm.RecordBatch>;
+      const batches: arrow.RecordBatch[] = [];
+      for await (const batch of stream) {
+        batches.push(...arrow.tableFromIPC(batch.intoIPCStream()).batches);
+      }
+      return new arrow.Table(batches);
+    },
+  ],
+  [
+    "ParquetReader",
+    async (buffer, options) => {
+      const batches: arrow.RecordBatch[] = [];
+      const reader = new wasm.ParquetReader(buffer, options);
+      for (const batch of Iterator.from(reader)) {
+        batches.push(...arrow.tableFromIPC(batch.intoIPCStream()).batches);
+      }
+      return new arrow.Table(batches);
+    },
+  ],
+];
+
+describe("type coercion", async (t) => {
+  const parquetBuffer = createParquet();
+  const typeCoercion: wasm.TypeCoercion = {
+    int64: "float64",
+    timestampUnit: "s",
+    downcastLargeTypes: true,
+    binaryAsString: true,
+  };
+
+  function checkCoerced(table: arrow.Table) {
+    expect(arrow.DataType.isFloat(table.getChild("int64")!.type)).toBeTruthy();
+    expect(table.getChild("int64")!.toJSON()).toStrictEqual([1, 2, 3]);
+
+    const timestamp = table.getChild("timestamp")!;
+    expect(timestamp.type.unit).toStrictEqual(arrow.TimeUnit.SECOND);
+
+    const largeUtf8 = table.getChild("largeUtf8")!;
+    expect(largeUtf8.type.typeId).toStrictEqual(arrow.Type.Utf8);
+
+    const binary = table.getChild("binary")!;
+    expect(binary.type.typeId).toStrictEqual(arrow.Type.Utf8);
+    expect(binary.toJSON()).toStrictEqual(["x", "y", "z"]);
+  }
+
+  it("keeps the original types by default", () => {
+    const table = arrow.tableFromIPC(
+      wasm.readParquet(parquetBuffer).intoIPCStream()
+    );
+    expect(table.getChild("int64")!.type.typeId).toStrictEqual(arrow.Type.Int);
+    expect(table.getChild("largeUtf8")!.type.typeId).toStrictEqual(
+      arrow.Type.LargeUtf8
+    );
+    expect(table.getChild("binary")!.type.typeId).toStrictEqual(
+      arrow.Type.Binary
+    );
+  });
+
+  it("readParquet", () => {
+    checkCoerced(
+      arrow.tableFromIPC(
+        wasm.readParquet(parquetBuffer, { typeCoercion }).intoIPCStream()
+      )
+    );
+  });
+
+  it("ParquetFile.read", async () => {
+    const file = await wasm.ParquetFile.fromFile(new Blob([parquetBuffer]));
+    checkCoerced(
+      arrow.tableFromIPC((await file.read({ typeCoercion })).intoIPCStream())
+    );
+  });
+
+  it("applies filters to the original types", () => {
+    const table = arrow.tableFromIPC(
+      wasm
+        .readParquet(parquetBuffer, {
+          typeCoercion,
+          filter: { op: ">", column: "int64", value: 1n },
+        })
+        .intoIPCStream()
+    );
+    expect(table.getChild("int64")!.toJSON()).toStrictEqual([2, 3]);
+  });
+});
+
+describe("type coercion of decimal, large and nested types", async (t) => {
+  const parquetBuffer = createNestedParquet();
+
+  it.each(readers)("%s", async (_name, read) => {
+    const table = await read(parquetBuffer, {
+      typeCoercion: { decimal: "float64", downcastLargeTypes: true },
+    });
+
+    const decimal = table.getChild("decimal")!;
+    expect(arrow.DataType.isFloat(decimal.type)).toBeTruthy();
+    expect(decimal.toJSON()).toStrictEqual([1.23, 4.56]);
+
+    const largeBinary = table.getChild("largeBinary")!;
+    expect(largeBinary.type.typeId).toStrictEqual(arrow.Type.Binary);
+
+    const map = table.getChild("map")!;
+    expect(map.type.typeId).toStrictEqual(arrow.Type.Map);
+    expect(map.type.valueType.typeId).toStrictEqual(arrow.Type.Utf8);
+
+    const dictionary = table.getChild("dictionary")!;
+    expect(dictionary.type.dictionary.typeId).toStrictEqual(arrow.Type.Utf8);
+    expect(dictionary.toJSON()).toStrictEqual(["a", "b"]);
+  });
+
+  it.each(readers)("%s casts decimals to strings", async (_name, read) => {
+    const table = await read(parquetBuffer, {
+      typeCoercion: { decimal: "string" },
+    });
+    const decimal = table.getChild("decimal")!;
+    expect(decimal.type.typeId).toStrictEqual(arrow.Type.Utf8);
+    expect(decimal.toJSON()).toStrictEqual(["1.23", "4.56"]);
+  });
+});
@@ -1,7 +1,7 @@
 import { expect } from "vitest";
 import { readFileSync } from "fs";
 import { tableFromIPC, Table } from "apache-arrow";
-import fastify, { FastifyInstance } from "fastify";
+import fastify, { FastifyInstance, onRequestAsyncHookHandler } from "fastify";
 import fastifyStatic from "@fastify/static";
 import { join } from "path";
 const dataDir = "tests/data";
@@ -65,13 +65,63 @@ export function readExpectedArrowData(): Table {
   return tableFromIPC(buffer);
 }
 
-export async function temporaryServer() {
+/**
+ * Serve the test data directory on a random port.
+ *
+ * - `requestedRanges`: The `Range` header of every request is appended to it.
+ * - `requiredHeaders`: Requests without these header values are rejected with a 401.
+ * - `onRequest`: A hook run before every request is served.
+ */
+export async function temporaryServer(
+  options: {
+    requestedRanges?: string[];
+    requiredHeaders?: Record<string, string>;
+    onRequest?: onRequestAsyncHookHandler;
+  } = {}
+) {
+  const { requestedRanges, requiredHeaders, onRequest } = options;
   const server = fastify().register(fastifyStatic, {
     root: join(__dirname, "../data"),
   });
+  if (requestedRanges) {
+    server.addHook("onRequest", async (request) => {
+      requestedRanges.push(request.headers.range ?? "");
+    });
+  }
+  if (requiredHeaders) {
+    server.addHook("onRequest", async (request, reply) => {
+      for (const [name, value] of Object.entries(requiredHeaders)) {
+        if (request.headers[name.toLowerCase()] !== value) {
+          reply.code(401).send();
+          return reply;
+        }
+      }
+    });
+  }
+  if (onRequest) {
+    server.addHook("onRequest", onRequest);
+  }
   await server.listen({
     port: 0,
     host: "localhost",
   });
   return server as FastifyInstance;
 }
+
+/**
+ * Run `test` against a temporary server for the test data directory, closing the server once
+ * `test` settles. `test` receives the base URL of the server. See {@link temporaryServer} for
+ * the options.
+ */
+export async function withServer<T>(
+  options: Parameters<typeof temporaryServer>[0],
+  test: (baseUrl: string) => Promise<T>
+): Promise<T> {
+  const server = await temporaryServer(options);
+  try {
+    const listeningPort = server.addresses()[0].port;
+    return await test(`http://localhost:${listeningPort}`);
+  } finally {
+    await server.close();
+  }
+}